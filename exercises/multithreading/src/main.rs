@@ -1,8 +1,6 @@
-mod thread_pool;
-
 use std::{sync::mpsc, thread::JoinHandle};
 
-use thread_pool::ThreadPool;
+use multithreading::thread_pool::ThreadPool;
 
 fn simple_multithreading() {
     let mut thd_handles = Vec::<JoinHandle<()>>::new();
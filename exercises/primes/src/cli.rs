@@ -0,0 +1,242 @@
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+
+use clap::error::ErrorKind;
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use primes_lib::counting::{NTH_PRIME_LIMIT, PRIME_PI_LIMIT};
+
+use crate::format::{Details, Format};
+use crate::input::{parse_number, InputError};
+
+/// `count` sieves ranges up to this wide; wider ones are counted with `prime_pi`,
+/// which only goes up to `PRIME_PI_LIMIT`.
+pub const COUNT_SIEVE_WIDTH: u64 = 100_000_000;
+
+const EXIT_CODES: &str = "\
+Exit codes:
+  0  success - for `check`, every number is prime
+  1  `check` found a number that is not prime, or `next`/`prev` found no prime
+  2  invalid arguments
+  3  the output could not be written
+  4  an input was empty
+  5  an input was not a number
+  6  an input was negative
+  7  an input did not fit in 64 bits
+  8  the input file or stdin could not be read
+With several invalid inputs the first one decides.";
+
+/// Prime number tools built on primes_lib.
+///
+/// `primes N...` without a command is the same as `primes check N...`.
+#[derive(Debug, Parser)]
+#[command(
+    name = "primes",
+    version,
+    about,
+    override_usage = "primes [OPTIONS] <COMMAND>\n       primes [OPTIONS] <N>...",
+    after_help = EXIT_CODES,
+    arg_required_else_help = true
+)]
+pub struct Cli {
+    /// Refuse to process anything if one of the inputs is invalid
+    #[arg(long, global = true, overrides_with = "keep_going")]
+    strict: bool,
+    /// Report invalid inputs and carry on with the others (the default)
+    #[arg(long, global = true, overrides_with = "strict")]
+    keep_going: bool,
+    /// Numbers to check when no command is given
+    #[arg(value_name = "N", allow_negative_numbers = true)]
+    numbers: Vec<String>,
+    #[command(flatten)]
+    output: Output,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// How `check` prints its results.
+#[derive(Debug, Clone, Default, Args)]
+pub struct Output {
+    /// Output format
+    #[arg(long, value_enum, default_value_t = Format::Plain)]
+    pub format: Format,
+    /// Add the prime factorization of each number
+    #[arg(long)]
+    pub factors: bool,
+    /// Add the primes just below and above each number
+    #[arg(long, visible_alias = "neighbours")]
+    pub neighbors: bool,
+}
+
+impl Output {
+    pub fn details(&self) -> Details {
+        Details {
+            factors: self.factors,
+            neighbors: self.neighbors,
+        }
+    }
+}
+
+/// What to do with the valid inputs when some of the others are invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorMode {
+    Strict,
+    KeepGoing,
+}
+
+impl Cli {
+    pub fn error_mode(&self) -> ErrorMode {
+        if self.strict {
+            ErrorMode::Strict
+        } else {
+            ErrorMode::KeepGoing
+        }
+    }
+
+    /// The command to run - `check` for bare numbers.
+    ///
+    /// Fails if there is neither a command nor a number, e.g. for `primes --strict`.
+    pub fn into_command(self) -> Result<Command, clap::Error> {
+        match self.command {
+            Some(command) => Ok(command),
+            None if self.numbers.is_empty() => Err(Cli::command().error(
+                ErrorKind::MissingSubcommand,
+                "a command or the numbers to check are required",
+            )),
+            None => Ok(Command::Check {
+                numbers: self.numbers,
+                input: None,
+                output: self.output,
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Tests each number for primality - `check -` reads one number per line from stdin
+    Check {
+        #[arg(
+            value_name = "N",
+            required_unless_present = "input",
+            allow_negative_numbers = true
+        )]
+        numbers: Vec<String>,
+        /// Read one number per line from FILE
+        #[arg(long, value_name = "FILE", conflicts_with = "numbers")]
+        input: Option<PathBuf>,
+        #[command(flatten)]
+        output: Output,
+    },
+    /// Lists the primes between two bounds, both included
+    List {
+        #[arg(long, default_value_t = 0, value_parser = parse_number)]
+        from: u64,
+        #[arg(long, value_parser = parse_number)]
+        to: u64,
+    },
+    /// Lists the first K primes
+    First {
+        #[arg(value_parser = parse_number)]
+        k: u64,
+    },
+    /// Counts the primes in a range: `A..B`, `A..=B`, `..B` or just `N` for `..=N`
+    #[command(visible_alias = "pi")]
+    Count {
+        #[arg(value_parser = parse_count_range)]
+        range: RangeInclusive<u64>,
+    },
+    /// Prints the K-th prime, counting from `nth 1` = 2
+    Nth {
+        #[arg(value_parser = parse_index)]
+        k: u64,
+    },
+    /// Prints the smallest prime greater than N
+    Next {
+        #[arg(value_parser = parse_number)]
+        n: u64,
+    },
+    /// Prints the largest prime smaller than N
+    Prev {
+        #[arg(value_parser = parse_number)]
+        n: u64,
+    },
+    /// Prints the prime factors of each number, repeated by multiplicity
+    Factor {
+        #[arg(value_name = "N", required = true, allow_negative_numbers = true)]
+        numbers: Vec<String>,
+    },
+    /// Starts an interactive session that takes the other commands, numbers and ranges
+    Repl,
+}
+
+fn parse_index(s: &str) -> Result<u64, String> {
+    match parse_number(s) {
+        Ok(0) => Err("primes are counted from 1".to_string()),
+        Ok(k) if k > NTH_PRIME_LIMIT => Err(format!(
+            "the largest supported index is {}",
+            NTH_PRIME_LIMIT
+        )),
+        Ok(k) => Ok(k),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Parses `A..B`, `A..=B`, `..B`, `..=B` or `N`, the last meaning `0..=N`.
+///
+/// Exclusive ranges become inclusive ones; an empty range stays empty.
+pub fn parse_range(s: &str) -> Result<RangeInclusive<u64>, String> {
+    let number = |part: &str| {
+        parse_number(part).map_err(|e: InputError| format!("invalid bound `{}`: {}", part, e))
+    };
+
+    let Some((start, end)) = s.split_once("..") else {
+        return Ok(0..=number(s)?);
+    };
+    let start = if start.is_empty() { 0 } else { number(start)? };
+    let end = match end.strip_prefix('=') {
+        Some(end) => number(end)?,
+        None => match number(end)?.checked_sub(1) {
+            Some(end) => end,
+            None => return Ok(RangeInclusive::new(1, 0)),
+        },
+    };
+    Ok(start..=end)
+}
+
+/// [`parse_range`] for `count`: a range wider than [`COUNT_SIEVE_WIDTH`] must end
+/// at or below `PRIME_PI_LIMIT`.
+fn parse_count_range(s: &str) -> Result<RangeInclusive<u64>, String> {
+    let range = parse_range(s)?;
+    let width = range.end().saturating_sub(*range.start());
+    if width > COUNT_SIEVE_WIDTH && *range.end() > PRIME_PI_LIMIT {
+        return Err(format!(
+            "a range ending above {} can be at most {} wide",
+            PRIME_PI_LIMIT, COUNT_SIEVE_WIDTH
+        ));
+    }
+    Ok(range)
+}
+
+#[cfg(test)]
+mod tests_cli {
+    use crate::cli::{parse_range, Cli};
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("100..200"), Ok(100..=199));
+        assert_eq!(parse_range("100..=200"), Ok(100..=200));
+        assert_eq!(parse_range("..=30"), Ok(0..=30));
+        assert_eq!(parse_range("..30"), Ok(0..=29));
+        assert_eq!(parse_range("1000"), Ok(0..=1000));
+        assert!(parse_range("5..0").unwrap().is_empty());
+        assert!(parse_range("5..").is_err());
+        assert!(parse_range("a..5").is_err());
+        assert!(parse_range("-3").is_err());
+    }
+}
@@ -0,0 +1,466 @@
+use std::fs::File;
+use std::io::{self, BufReader, Write};
+use std::process::ExitCode;
+
+use primes_lib::counting::{nth_prime, prime_pi};
+use primes_lib::factor::factorize;
+use primes_lib::math_utils::{all_primes, next_prime_after, prev_prime_before, Primes};
+
+use crate::cli::{Command, ErrorMode, Output, COUNT_SIEVE_WIDTH};
+use crate::format::{Record, RecordWriter};
+use crate::input::{parse_number, InputError};
+use crate::repl;
+use crate::stream::{self, check_lines};
+
+/// How a command ended - see the exit codes in `primes --help`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Success,
+    NotPrime,
+    NotFound,
+    OutputFailed,
+    InvalidInput(InputError),
+    ReadFailed,
+}
+
+impl Status {
+    pub fn exit_code(self) -> ExitCode {
+        match self {
+            Status::Success => ExitCode::SUCCESS,
+            Status::NotPrime | Status::NotFound => ExitCode::from(1),
+            Status::OutputFailed => ExitCode::from(3),
+            Status::InvalidInput(e) => ExitCode::from(e.exit_code()),
+            Status::ReadFailed => ExitCode::from(8),
+        }
+    }
+}
+
+/// Runs `command`, writing its results to `out` line by line and the inputs it
+/// rejects to `err`.
+pub fn run(
+    command: &Command,
+    mode: ErrorMode,
+    out: &mut impl Write,
+    err: &mut impl Write,
+) -> io::Result<Status> {
+    match command {
+        Command::Check {
+            input: Some(path),
+            output,
+            ..
+        } => match File::open(path) {
+            Ok(file) => {
+                let threads = stream::default_threads();
+                check_lines(BufReader::new(file), threads, output, mode, out, err)
+            }
+            Err(e) => {
+                writeln!(err, "primes: cannot read {}: {}", path.display(), e)?;
+                Ok(Status::ReadFailed)
+            }
+        },
+        Command::Check {
+            numbers, output, ..
+        } if numbers == &["-"] => {
+            let threads = stream::default_threads();
+            check_lines(io::stdin().lock(), threads, output, mode, out, err)
+        }
+        Command::Check {
+            numbers, output, ..
+        } => check(numbers, output, mode, out, err),
+        Command::List { from, to } => {
+            // u64::MAX is composite, so the exclusive end may saturate
+            let primes = Primes::in_range(*from..to.saturating_add(1));
+            write_lines(primes, out).map(|_| Status::Success)
+        }
+        Command::First { k } => {
+            let k = usize::try_from(*k).unwrap_or(usize::MAX);
+            write_lines(all_primes().take(k), out).map(|_| Status::Success)
+        }
+        Command::Count { range } => {
+            let count = if range.is_empty() {
+                0
+            } else if range.end() - range.start() <= COUNT_SIEVE_WIDTH {
+                // u64::MAX is composite, so the exclusive end may saturate
+                Primes::in_range(*range.start()..range.end().saturating_add(1)).count() as u64
+            } else {
+                let below_start = range.start().checked_sub(1).map_or(0, prime_pi);
+                prime_pi(*range.end()) - below_start
+            };
+            writeln!(out, "{}", count).map(|_| Status::Success)
+        }
+        Command::Nth { k } => writeln!(out, "{}", nth_prime(*k)).map(|_| Status::Success),
+        Command::Next { n } => match next_prime_after(*n) {
+            Some(p) => writeln!(out, "{}", p).map(|_| Status::Success),
+            None => {
+                writeln!(err, "primes: no prime above {} fits in 64 bits", n)?;
+                Ok(Status::NotFound)
+            }
+        },
+        Command::Prev { n } => match prev_prime_before(*n) {
+            Some(p) => writeln!(out, "{}", p).map(|_| Status::Success),
+            None => {
+                writeln!(err, "primes: no prime below {}", n)?;
+                Ok(Status::NotFound)
+            }
+        },
+        Command::Factor { numbers } => {
+            let invalid = for_each_number(numbers, mode, err, |n| factor(n, out))?;
+            Ok(invalid.map_or(Status::Success, Status::InvalidInput))
+        }
+        Command::Repl => repl::run(mode, out, err),
+    }
+}
+
+/// Primes, non-primes and invalid inputs seen so far by `check`.
+#[derive(Debug, Default)]
+pub struct Tally {
+    pub primes: u64,
+    pub not_primes: u64,
+    pub invalid: u64,
+    first_error: Option<InputError>,
+}
+
+impl Tally {
+    pub fn add(&mut self, record: &Record) {
+        match record.value {
+            Ok(_) if record.prime => self.primes += 1,
+            Ok(_) => self.not_primes += 1,
+            Err(e) => {
+                self.invalid += 1;
+                self.first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    /// The first invalid input decides; otherwise whether all were prime.
+    pub fn status(&self) -> Status {
+        match self.first_error {
+            Some(e) => Status::InvalidInput(e),
+            None if self.not_primes > 0 => Status::NotPrime,
+            None => Status::Success,
+        }
+    }
+}
+
+fn check(
+    tokens: &[String],
+    output: &Output,
+    mode: ErrorMode,
+    out: &mut impl Write,
+    err: &mut impl Write,
+) -> io::Result<Status> {
+    if mode == ErrorMode::Strict {
+        if let Some(e) = validate(tokens, err)? {
+            return Ok(Status::InvalidInput(e));
+        }
+    }
+
+    let mut writer = RecordWriter::begin(output.format, output.details(), out)?;
+    let mut tally = Tally::default();
+    for token in tokens {
+        let record = Record::new(token, output.details());
+        if let Err(e) = record.value {
+            report_invalid(err, token, e)?;
+        }
+        tally.add(&record);
+        writer.write(&record, out)?;
+    }
+    writer.finish(out)?;
+    Ok(tally.status())
+}
+
+/// Reports every invalid token on `err` and returns the error of the first one.
+fn validate(tokens: &[String], err: &mut impl Write) -> io::Result<Option<InputError>> {
+    let mut first_error = None;
+    for token in tokens {
+        if let Err(e) = parse_number(token) {
+            report_invalid(err, token, e)?;
+            first_error.get_or_insert(e);
+        }
+    }
+    Ok(first_error)
+}
+
+/// Calls `f` for every token that parses, in order, and reports the others on
+/// `err`. Returns the error of the first invalid token.
+///
+/// In strict mode every token is validated first, and `f` is not called at all
+/// if one of them is invalid.
+fn for_each_number(
+    tokens: &[String],
+    mode: ErrorMode,
+    err: &mut impl Write,
+    mut f: impl FnMut(u64) -> io::Result<()>,
+) -> io::Result<Option<InputError>> {
+    if mode == ErrorMode::Strict {
+        if let Some(e) = validate(tokens, err)? {
+            return Ok(Some(e));
+        }
+    }
+
+    let mut first_error = None;
+
+    for token in tokens {
+        match parse_number(token) {
+            Ok(n) => f(n)?,
+            Err(e) => {
+                report_invalid(err, token, e)?;
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    Ok(first_error)
+}
+
+pub fn report_invalid(err: &mut impl Write, token: &str, error: InputError) -> io::Result<()> {
+    writeln!(err, "primes: invalid number {:?}: {}", token, error)
+}
+
+/// One line in the style of coreutils `factor`: `12: 2 2 3`.
+fn factor(n: u64, out: &mut impl Write) -> io::Result<()> {
+    write!(out, "{}:", n)?;
+    for (p, exponent) in factorize(n) {
+        for _ in 0..exponent {
+            write!(out, " {}", p)?;
+        }
+    }
+    writeln!(out)
+}
+
+fn write_lines(values: impl Iterator<Item = u64>, out: &mut impl Write) -> io::Result<()> {
+    for value in values {
+        writeln!(out, "{}", value)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests_commands {
+    use crate::cli::Cli;
+    use crate::commands::{run, Status};
+    use crate::input::InputError;
+    use clap::Parser;
+    use primes_lib::math_utils::is_prime;
+
+    fn run_with_errors(args: &[&str]) -> (Status, String, String) {
+        let cli = Cli::try_parse_from(["primes"].iter().chain(args)).unwrap();
+        let mode = cli.error_mode();
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        let status = run(&cli.into_command().unwrap(), mode, &mut out, &mut err).unwrap();
+        (
+            status,
+            String::from_utf8(out).unwrap(),
+            String::from_utf8(err).unwrap(),
+        )
+    }
+
+    fn run_args(args: &[&str]) -> (Status, String) {
+        let (status, out, err) = run_with_errors(args);
+        assert_eq!(err, "");
+        (status, out)
+    }
+
+    #[test]
+    fn test_check() {
+        assert_eq!(
+            run_args(&["check", "2", "97"]),
+            (Status::Success, "2 is prime\n97 is prime\n".to_string())
+        );
+        assert_eq!(
+            run_args(&["check", "7", "91", "1"]),
+            (
+                Status::NotPrime,
+                "7 is prime\n91 is not prime\n1 is not prime\n".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_list_and_first() {
+        assert_eq!(
+            run_args(&["list", "--from", "10", "--to", "29"]),
+            (Status::Success, "11\n13\n17\n19\n23\n29\n".to_string())
+        );
+        assert_eq!(run_args(&["list", "--from", "20", "--to", "10"]).1, "");
+        assert_eq!(
+            run_args(&[
+                "list",
+                "--to",
+                "18446744073709551615",
+                "--from",
+                "18446744073709551550"
+            ])
+            .1,
+            "18446744073709551557\n"
+        );
+        assert_eq!(run_args(&["first", "5"]).1, "2\n3\n5\n7\n11\n");
+        assert_eq!(run_args(&["first", "0"]).1, "");
+    }
+
+    #[test]
+    fn test_count_and_nth() {
+        assert_eq!(run_args(&["count", "1000"]).1, "168\n");
+        assert_eq!(run_args(&["count", "100..200"]).1, "21\n");
+        assert_eq!(run_args(&["count", "2..=3"]).1, "2\n");
+        assert_eq!(run_args(&["count", "7..7"]).1, "0\n");
+        assert_eq!(run_args(&["nth", "1"]).1, "2\n");
+        assert_eq!(run_args(&["nth", "1000000"]).1, "15485863\n");
+    }
+
+    #[test]
+    fn test_count_near_the_top() {
+        let expected = (18_446_744_073_709_551_000..=u64::MAX)
+            .filter(|&n| is_prime(n))
+            .count();
+        assert_eq!(
+            run_args(&["count", "18446744073709551000..=18446744073709551615"]).1,
+            format!("{}\n", expected)
+        );
+        assert_eq!(run_args(&["count", "1e19..1e19"]).1, "0\n");
+        // wider than COUNT_SIEVE_WIDTH - counted with prime_pi
+        assert_eq!(run_args(&["count", "10..=1e9"]).1, "50847530\n");
+    }
+
+    #[test]
+    fn test_next_and_prev() {
+        assert_eq!(run_args(&["next", "1000"]).1, "1009\n");
+        assert_eq!(run_args(&["next", "0"]).1, "2\n");
+        assert_eq!(run_args(&["prev", "1e3"]).1, "997\n");
+        assert_eq!(run_args(&["pi", "1e3"]).1, "168\n");
+        assert_eq!(
+            run_with_errors(&["prev", "2"]),
+            (
+                Status::NotFound,
+                String::new(),
+                "primes: no prime below 2\n".to_string()
+            )
+        );
+        assert_eq!(
+            run_with_errors(&["next", "18446744073709551557"]).0,
+            Status::NotFound
+        );
+    }
+
+    #[test]
+    fn test_factor() {
+        assert_eq!(
+            run_args(&["factor", "12", "97", "1"]),
+            (Status::Success, "12: 2 2 3\n97: 97\n1:\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_input_file() {
+        let path = std::env::temp_dir().join(format!("primes_cli_{}.txt", std::process::id()));
+        std::fs::write(&path, "13\n\nfoo\n15\n").unwrap();
+        let result = run_with_errors(&["check", "--input", path.to_str().unwrap()]);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            result,
+            (
+                Status::InvalidInput(InputError::NotANumber),
+                "13 is prime\n15 is not prime\n".to_string(),
+                "primes: line 3: invalid number \"foo\": not a number\n\
+                 primes: 1 prime, 1 not prime, 1 invalid\n"
+                    .to_string()
+            )
+        );
+
+        let (status, out, err) = run_with_errors(&["check", "--input", "/nonexistent/numbers"]);
+        assert_eq!((status, out.as_str()), (Status::ReadFailed, ""));
+        assert!(err.starts_with("primes: cannot read /nonexistent/numbers: "));
+    }
+
+    #[test]
+    fn test_formats() {
+        assert_eq!(
+            run_args(&["check", "--format", "csv", "7", "9"]),
+            (
+                Status::NotPrime,
+                "input,value,prime,error\n7,7,true,\n9,9,false,\n".to_string()
+            )
+        );
+        assert_eq!(
+            run_args(&["7", "--format", "ndjson", "--factors"]).1,
+            "{\"input\":\"7\",\"value\":7,\"prime\":true,\
+             \"factors\":[{\"prime\":7,\"exponent\":1}],\"error\":null}\n"
+        );
+
+        let (status, out, err) = run_with_errors(&["check", "--format", "json", "4", "x"]);
+        assert_eq!(status, Status::InvalidInput(InputError::NotANumber));
+        assert!(out.starts_with("[\n") && out.ends_with("\n]\n"));
+        assert!(out.contains("\"error\":\"not a number\""));
+        assert_eq!(err, "primes: invalid number \"x\": not a number\n");
+    }
+
+    #[test]
+    fn test_without_command() {
+        assert_eq!(
+            run_args(&["12", "13"]),
+            (
+                Status::NotPrime,
+                "12 is not prime\n13 is prime\n".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_keep_going() {
+        let (status, out, err) = run_with_errors(&["12", "abc", "", "-5", "13"]);
+        assert_eq!(status, Status::InvalidInput(InputError::NotANumber));
+        assert_eq!(out, "12 is not prime\n13 is prime\n");
+        assert_eq!(
+            err,
+            "primes: invalid number \"abc\": not a number\n\
+             primes: invalid number \"\": empty input\n\
+             primes: invalid number \"-5\": negative numbers are not supported\n"
+        );
+
+        let (status, out, _) = run_with_errors(&["factor", "12", "99999999999999999999"]);
+        assert_eq!(status, Status::InvalidInput(InputError::Overflow));
+        assert_eq!(out, "12: 2 2 3\n");
+    }
+
+    #[test]
+    fn test_strict() {
+        let (status, out, err) = run_with_errors(&["--strict", "check", "7", "x", "-1"]);
+        assert_eq!(status, Status::InvalidInput(InputError::NotANumber));
+        assert_eq!(out, "");
+        assert_eq!(err.lines().count(), 2);
+
+        // the last of --strict and --keep-going wins
+        let (status, out, _) = run_with_errors(&["check", "--strict", "--keep-going", "7", "x"]);
+        assert_eq!(status, Status::InvalidInput(InputError::NotANumber));
+        assert_eq!(out, "7 is prime\n");
+
+        assert_eq!(
+            run_args(&["factor", "--strict", "12"]),
+            (Status::Success, "12: 2 2 3\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        for args in [
+            &[][..],
+            &["check"],
+            &["check", "7", "--input", "numbers.txt"],
+            &["nth", "0"],
+            &["first", "-1"],
+            &["list", "--from", "3"],
+            &["count", "5.."],
+            &["count", "0..=18446744073709551615"],
+            &["count", "1e14..=100000100000001"],
+            &["pi", "1e19"],
+            &["nth", "18446744073709551615"],
+            &["nth", "3204941750803"],
+            &["--strict"],
+        ] {
+            let error = Cli::try_parse_from(["primes"].iter().chain(args))
+                .and_then(Cli::into_command)
+                .unwrap_err();
+            assert_eq!(error.exit_code(), 2, "{:?}", args);
+        }
+    }
+}
@@ -0,0 +1,376 @@
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+
+use primes_lib::factor::factorize;
+use primes_lib::math_utils::{is_prime, next_prime_after, prev_prime_before};
+
+use crate::input::{parse_number, InputError};
+
+/// How `check` prints its records.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// `12 is not prime` - invalid inputs only go to stderr
+    #[default]
+    Plain,
+    /// One JSON array of record objects
+    Json,
+    /// One JSON record object per line
+    Ndjson,
+    /// A header row, then one row per record
+    Csv,
+}
+
+/// Optional fields of a [`Record`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Details {
+    pub factors: bool,
+    pub neighbors: bool,
+}
+
+/// Everything `check` reports about one input token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub input: String,
+    pub value: Result<u64, InputError>,
+    /// `false` for invalid inputs
+    pub prime: bool,
+    /// `(prime, exponent)` pairs, for valid inputs when asked for
+    pub factors: Option<Vec<(u64, u32)>>,
+    /// The primes just below and above the value, for valid inputs when asked for
+    pub neighbors: Option<(Option<u64>, Option<u64>)>,
+}
+
+impl Record {
+    pub fn new(input: &str, details: Details) -> Record {
+        let value = parse_number(input);
+        let valid = value.as_ref().ok();
+        Record {
+            input: input.to_string(),
+            value,
+            prime: valid.is_some_and(|&n| is_prime(n)),
+            factors: valid.filter(|_| details.factors).map(|&n| factorize(n)),
+            neighbors: valid
+                .filter(|_| details.neighbors)
+                .map(|&n| (prev_prime_before(n), next_prime_after(n))),
+        }
+    }
+
+    /// `None` for invalid inputs.
+    pub fn verdict(&self) -> Option<bool> {
+        self.value.ok().map(|_| self.prime)
+    }
+
+    /// One JSON object on one line, keys in schema order.
+    fn write_json(&self, out: &mut impl Write) -> io::Result<()> {
+        let optional = |value: Option<u64>| value.map_or("null".to_string(), |v| v.to_string());
+
+        // only the input needs escaping
+        let input = serde_json::to_string(&self.input).map_err(io::Error::other)?;
+        write!(
+            out,
+            "{{\"input\":{},\"value\":{},\"prime\":{}",
+            input,
+            optional(self.value.ok()),
+            self.verdict().map_or("null".to_string(), |p| p.to_string())
+        )?;
+        if let Some(factors) = &self.factors {
+            write!(out, ",\"factors\":[")?;
+            for (i, (p, exponent)) in factors.iter().enumerate() {
+                let separator = if i == 0 { "" } else { "," };
+                write!(
+                    out,
+                    "{}{{\"prime\":{},\"exponent\":{}}}",
+                    separator, p, exponent
+                )?;
+            }
+            write!(out, "]")?;
+        }
+        if let Some((previous, next)) = self.neighbors {
+            write!(
+                out,
+                ",\"previous\":{},\"next\":{}",
+                optional(previous),
+                optional(next)
+            )?;
+        }
+        match self.value {
+            Ok(_) => write!(out, ",\"error\":null}}"),
+            Err(e) => write!(out, ",\"error\":\"{}\"}}", e),
+        }
+    }
+}
+
+/// `2^2 * 3`; the empty factorizations of 0 and 1 are written as the number.
+fn product(n: u64, factors: &[(u64, u32)]) -> String {
+    if factors.is_empty() {
+        return n.to_string();
+    }
+    let powers: Vec<String> = factors
+        .iter()
+        .map(|&(p, exponent)| match exponent {
+            1 => p.to_string(),
+            e => format!("{}^{}", p, e),
+        })
+        .collect();
+    powers.join(" * ")
+}
+
+/// Writes records one at a time in a [`Format`], so a stream of any length can
+/// be printed without holding on to them.
+pub struct RecordWriter {
+    format: Format,
+    details: Details,
+    records: u64,
+}
+
+impl RecordWriter {
+    /// Starts the output - the CSV header is written right away.
+    pub fn begin(
+        format: Format,
+        details: Details,
+        out: &mut impl Write,
+    ) -> io::Result<RecordWriter> {
+        if format == Format::Csv {
+            let mut header = vec!["input", "value", "prime"];
+            if details.factors {
+                header.push("factors");
+            }
+            if details.neighbors {
+                header.extend(["previous", "next"]);
+            }
+            header.push("error");
+            writeln!(out, "{}", header.join(","))?;
+        }
+        Ok(RecordWriter {
+            format,
+            details,
+            records: 0,
+        })
+    }
+
+    pub fn write(&mut self, record: &Record, out: &mut impl Write) -> io::Result<()> {
+        self.records += 1;
+        match self.format {
+            Format::Plain => write_plain(record, out),
+            Format::Json => {
+                let separator = if self.records == 1 { "[" } else { "," };
+                writeln!(out, "{}", separator)?;
+                record.write_json(out)
+            }
+            Format::Ndjson => {
+                record.write_json(out)?;
+                writeln!(out)
+            }
+            Format::Csv => self.write_csv(record, out),
+        }
+    }
+
+    /// Closes the JSON array; the other formats need no trailer.
+    pub fn finish(self, out: &mut impl Write) -> io::Result<()> {
+        match self.format {
+            Format::Json if self.records == 0 => writeln!(out, "[]"),
+            Format::Json => writeln!(out, "\n]"),
+            _ => Ok(()),
+        }
+    }
+
+    fn write_csv(&self, record: &Record, out: &mut impl Write) -> io::Result<()> {
+        let optional = |value: Option<u64>| value.map_or(String::new(), |v| v.to_string());
+
+        let mut row = vec![
+            csv_field(&record.input),
+            optional(record.value.ok()),
+            record.verdict().map_or(String::new(), |p| p.to_string()),
+        ];
+        if self.details.factors {
+            let factors = match (&record.factors, record.value) {
+                (Some(factors), Ok(n)) => product(n, factors).replace(' ', ""),
+                _ => String::new(),
+            };
+            row.push(factors);
+        }
+        if self.details.neighbors {
+            let (previous, next) = record.neighbors.unwrap_or((None, None));
+            row.extend([optional(previous), optional(next)]);
+        }
+        row.push(
+            record
+                .value
+                .err()
+                .map_or(String::new(), |e| csv_field(&e.to_string())),
+        );
+        writeln!(out, "{}", row.join(","))
+    }
+}
+
+fn write_plain(record: &Record, out: &mut impl Write) -> io::Result<()> {
+    let Ok(n) = record.value else {
+        return Ok(());
+    };
+
+    let verdict = if record.prime {
+        "is prime"
+    } else {
+        "is not prime"
+    };
+    write!(out, "{} {}", n, verdict)?;
+    if let Some(factors) = &record.factors {
+        write!(out, ", {} = {}", n, product(n, factors))?;
+    }
+    if let Some((previous, next)) = record.neighbors {
+        match previous {
+            Some(p) => write!(out, ", previous prime {}", p)?,
+            None => write!(out, ", no previous prime")?,
+        }
+        match next {
+            Some(p) => write!(out, ", next prime {}", p)?,
+            None => write!(out, ", no next prime below 2^64")?,
+        }
+    }
+    writeln!(out)
+}
+
+/// Quotes a field that holds a separator, a quote or a line break (RFC 4180).
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// The record schema that downstream tools rely on. Change these tests only
+/// together with a note to the consumers.
+#[cfg(test)]
+mod tests_format {
+    use crate::format::{Details, Format, Record, RecordWriter};
+    use serde_json::{json, Value};
+
+    const ALL: Details = Details {
+        factors: true,
+        neighbors: true,
+    };
+
+    fn render(format: Format, details: Details, inputs: &[&str]) -> String {
+        let mut out = Vec::new();
+        let mut writer = RecordWriter::begin(format, details, &mut out).unwrap();
+        for input in inputs {
+            writer
+                .write(&Record::new(input, details), &mut out)
+                .unwrap();
+        }
+        writer.finish(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    /// Every JSON record has, in this order:
+    ///
+    /// - `input`: the token as given (string)
+    /// - `value`: the parsed number, `null` if invalid (unsigned 64-bit integer)
+    /// - `prime`: the verdict, `null` if invalid (boolean)
+    /// - `factors`: only with `--factors` - `{prime, exponent}` objects in ascending
+    ///   order of prime, empty for 0 and 1; absent if invalid
+    /// - `previous`, `next`: only with `--neighbors` - the closest primes below and
+    ///   above, `null` if there is none in `u64`; absent if invalid
+    /// - `error`: why the input is invalid, `null` if it is valid (string)
+    #[test]
+    fn test_json_schema() {
+        let out = render(Format::Ndjson, ALL, &["12", "2", "-4"]);
+        let records: Vec<Value> = out
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(
+            records,
+            [
+                json!({
+                    "input": "12", "value": 12, "prime": false,
+                    "factors": [{"prime": 2, "exponent": 2}, {"prime": 3, "exponent": 1}],
+                    "previous": 11, "next": 13, "error": null
+                }),
+                json!({
+                    "input": "2", "value": 2, "prime": true,
+                    "factors": [{"prime": 2, "exponent": 1}],
+                    "previous": null, "next": 3, "error": null
+                }),
+                json!({
+                    "input": "-4", "value": null, "prime": null,
+                    "error": "negative numbers are not supported"
+                }),
+            ]
+        );
+
+        let line = out.lines().next().unwrap();
+        let positions: Vec<usize> = [
+            "input", "value", "prime", "factors", "previous", "next", "error",
+        ]
+        .iter()
+        .map(|key| line.find(&format!("\"{}\":", key)).unwrap())
+        .collect();
+        assert!(positions.windows(2).all(|w| w[0] < w[1]), "{}", line);
+    }
+
+    #[test]
+    fn test_json_optional_fields() {
+        let out = render(Format::Ndjson, Details::default(), &["1"]);
+        assert_eq!(
+            out,
+            "{\"input\":\"1\",\"value\":1,\"prime\":false,\"error\":null}\n"
+        );
+
+        let out = render(Format::Ndjson, ALL, &["18446744073709551557"]);
+        let record: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(record["value"], json!(18_446_744_073_709_551_557u64));
+        assert_eq!(record["next"], Value::Null);
+        assert_eq!(record["factors"].as_array().unwrap().len(), 1);
+    }
+
+    /// `json` is one array holding the same records as `ndjson`, one per line.
+    #[test]
+    fn test_json_array() {
+        let array: Value =
+            serde_json::from_str(&render(Format::Json, ALL, &["4", "x", "7"])).unwrap();
+        let lines: Vec<Value> = render(Format::Ndjson, ALL, &["4", "x", "7"])
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(array, Value::Array(lines));
+
+        assert_eq!(render(Format::Json, ALL, &[]), "[]\n");
+    }
+
+    /// `csv` starts with a header row. The columns are `input,value,prime`, then
+    /// `factors` with `--factors` (`2^2*3`, the number itself for 0 and 1), then
+    /// `previous,next` with `--neighbors`, and `error` last. Missing values are
+    /// empty fields, and fields are quoted as in RFC 4180.
+    #[test]
+    fn test_csv_schema() {
+        assert_eq!(
+            render(Format::Csv, ALL, &["12", "1", "a,\"b\"", "2"]),
+            "input,value,prime,factors,previous,next,error\n\
+             12,12,false,2^2*3,11,13,\n\
+             1,1,false,1,,2,\n\
+             \"a,\"\"b\"\"\",,,,,,not a number\n\
+             2,2,true,2,,3,\n"
+        );
+        assert_eq!(
+            render(Format::Csv, Details::default(), &["97", ""]),
+            "input,value,prime,error\n97,97,true,\n,,,empty input\n"
+        );
+    }
+
+    #[test]
+    fn test_plain() {
+        assert_eq!(
+            render(Format::Plain, Details::default(), &["7", "x", "8"]),
+            "7 is prime\n8 is not prime\n"
+        );
+        assert_eq!(
+            render(Format::Plain, ALL, &["12", "0"]),
+            "12 is not prime, 12 = 2^2 * 3, previous prime 11, next prime 13\n\
+             0 is not prime, 0 = 0, no previous prime, next prime 2\n"
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use std::error::Error;
+use std::fmt;
+use std::num::IntErrorKind;
+
+/// Why a token is not a number the commands can work with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputError {
+    Empty,
+    NotANumber,
+    Negative,
+    Overflow,
+}
+
+impl InputError {
+    /// Exit status for this kind of error - see `primes --help`.
+    pub fn exit_code(self) -> u8 {
+        match self {
+            InputError::Empty => 4,
+            InputError::NotANumber => 5,
+            InputError::Negative => 6,
+            InputError::Overflow => 7,
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Empty => write!(f, "empty input"),
+            InputError::NotANumber => write!(f, "not a number"),
+            InputError::Negative => write!(f, "negative numbers are not supported"),
+            InputError::Overflow => write!(f, "larger than {}", u64::MAX),
+        }
+    }
+}
+
+impl Error for InputError {}
+
+/// Parses a decimal `u64`, ignoring surrounding whitespace.
+///
+/// A power of ten may follow the digits, so `1e9` is a billion; the result
+/// still has to fit in a `u64`. `-0` is accepted as zero; any other negative
+/// number is [`InputError::Negative`] rather than [`InputError::NotANumber`],
+/// however large it is.
+pub fn parse_number(token: &str) -> Result<u64, InputError> {
+    let token = token.trim();
+    if token.is_empty() {
+        return Err(InputError::Empty);
+    }
+
+    if let Some(magnitude) = token.strip_prefix('-') {
+        return match parse_magnitude(magnitude) {
+            Ok(0) => Ok(0),
+            Ok(_) | Err(InputError::Overflow) => Err(InputError::Negative),
+            Err(_) => Err(InputError::NotANumber),
+        };
+    }
+
+    parse_magnitude(token)
+}
+
+fn parse_magnitude(token: &str) -> Result<u64, InputError> {
+    let (mantissa, exponent) = match token.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (token, None),
+    };
+    let mantissa = mantissa.parse::<u64>().map_err(|e| match e.kind() {
+        IntErrorKind::PosOverflow => InputError::Overflow,
+        _ => InputError::NotANumber,
+    })?;
+    let Some(exponent) = exponent else {
+        return Ok(mantissa);
+    };
+
+    if exponent.is_empty() || !exponent.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(InputError::NotANumber);
+    }
+    if mantissa == 0 {
+        return Ok(0);
+    }
+    exponent
+        .parse::<u32>()
+        .ok()
+        .and_then(|exponent| 10u64.checked_pow(exponent))
+        .and_then(|power| mantissa.checked_mul(power))
+        .ok_or(InputError::Overflow)
+}
+
+#[cfg(test)]
+mod tests_input {
+    use crate::input::{parse_number, InputError};
+
+    #[test]
+    fn test_parse_number() {
+        assert_eq!(parse_number("97"), Ok(97));
+        assert_eq!(parse_number(" 12\t"), Ok(12));
+        assert_eq!(parse_number("+5"), Ok(5));
+        assert_eq!(parse_number("-0"), Ok(0));
+        assert_eq!(parse_number("18446744073709551615"), Ok(u64::MAX));
+    }
+
+    #[test]
+    fn test_parse_number_with_exponent() {
+        assert_eq!(parse_number("1e9"), Ok(1_000_000_000));
+        assert_eq!(parse_number("25E2"), Ok(2_500));
+        assert_eq!(parse_number("7e0"), Ok(7));
+        assert_eq!(parse_number("1e19"), Ok(10_000_000_000_000_000_000));
+        assert_eq!(parse_number("0e99999999999"), Ok(0));
+        assert_eq!(parse_number("-0e5"), Ok(0));
+        assert_eq!(parse_number("1e20"), Err(InputError::Overflow));
+        assert_eq!(parse_number("2e19"), Err(InputError::Overflow));
+        assert_eq!(parse_number("1e99999999999"), Err(InputError::Overflow));
+        assert_eq!(parse_number("-1e9"), Err(InputError::Negative));
+        assert_eq!(parse_number("-1e99"), Err(InputError::Negative));
+        assert_eq!(parse_number("1.5e3"), Err(InputError::NotANumber));
+        assert_eq!(parse_number("1e-3"), Err(InputError::NotANumber));
+        assert_eq!(parse_number("1e+3"), Err(InputError::NotANumber));
+        assert_eq!(parse_number("1e"), Err(InputError::NotANumber));
+        assert_eq!(parse_number("e9"), Err(InputError::NotANumber));
+    }
+
+    #[test]
+    fn test_parse_number_errors() {
+        assert_eq!(parse_number(""), Err(InputError::Empty));
+        assert_eq!(parse_number("  "), Err(InputError::Empty));
+        assert_eq!(parse_number("abc"), Err(InputError::NotANumber));
+        assert_eq!(parse_number("t665"), Err(InputError::NotANumber));
+        assert_eq!(parse_number("1.5"), Err(InputError::NotANumber));
+        assert_eq!(parse_number("-"), Err(InputError::NotANumber));
+        assert_eq!(parse_number("--5"), Err(InputError::NotANumber));
+        assert_eq!(parse_number("-5"), Err(InputError::Negative));
+        assert_eq!(
+            parse_number("-99999999999999999999"),
+            Err(InputError::Negative)
+        );
+        assert_eq!(
+            parse_number("18446744073709551616"),
+            Err(InputError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_exit_codes_are_distinct() {
+        let errors = [
+            InputError::Empty,
+            InputError::NotANumber,
+            InputError::Negative,
+            InputError::Overflow,
+        ];
+        for (i, a) in errors.iter().enumerate() {
+            assert!(a.exit_code() > 3);
+            for b in &errors[i + 1..] {
+                assert_ne!(a.exit_code(), b.exit_code());
+            }
+        }
+    }
+}
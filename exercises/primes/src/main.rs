@@ -1,23 +1,45 @@
-use primes_lib::math_utils::is_prime;
+mod cli;
+mod commands;
+mod format;
+mod input;
+mod repl;
+mod stream;
 
+use std::io::{self, BufWriter, ErrorKind, Write};
+use std::process::ExitCode;
+
+use clap::Parser;
+
+use cli::Cli;
+use commands::Status;
 
 // EXTRA - Iterators
-// #[test]
-// fn test_all_primes() {
-//     // let primes = all_primes().take(10).collect::<Vec<u32>>();
-//     // assert_eq!(primes, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
-// }
+#[test]
+fn test_all_primes() {
+    use primes_lib::math_utils::all_primes;
+
+    let primes = all_primes().take(10).collect::<Vec<u64>>();
+    assert_eq!(primes, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+}
 
-fn main() {
-    let args: Vec<String> = std::env::args().collect();
-    println!("{:?}", args);
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let mode = cli.error_mode();
+    let command = cli.into_command().unwrap_or_else(|e| e.exit());
 
-    for args in &args[1..] {
-        let num = args.parse::<u64>().expect("Invalid number");
-        if is_prime(num) {
-            println!("{} is prime", num);
-        } else {
-            println!("{} is not prime", num);
+    let mut out = BufWriter::new(io::stdout().lock());
+    let mut err = io::stderr().lock();
+    let status = match commands::run(&command, mode, &mut out, &mut err).and_then(|status| {
+        out.flush()?;
+        Ok(status)
+    }) {
+        Ok(status) => status,
+        // the reader went away, e.g. `primes list --to 1000000 | head`
+        Err(e) if e.kind() == ErrorKind::BrokenPipe => Status::Success,
+        Err(e) => {
+            eprintln!("primes: {}", e);
+            Status::OutputFailed
         }
-    }
+    };
+    status.exit_code()
 }
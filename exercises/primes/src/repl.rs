@@ -0,0 +1,293 @@
+use std::env;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
+use rustyline::completion::Completer;
+use rustyline::config::Config;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+
+use crate::cli::{parse_range, Cli, Command, ErrorMode};
+use crate::commands::{self, Status};
+
+const PROMPT: &str = "primes> ";
+
+/// Kept in the home directory, so that it is shared between sessions.
+const HISTORY_FILE: &str = ".primes_history";
+const HISTORY_SIZE: usize = 1000;
+
+/// What Tab completes at the start of a line.
+const COMMANDS: &[&str] = &[
+    "check", "count", "exit", "factor", "first", "help", "list", "next", "nth", "pi", "prev",
+    "quit",
+];
+
+const HELP: &str = "\
+N...                  whether each number is prime
+A..B, A..=B, ..B      the primes in a range
+check N...            the same as N..., with the options of `primes check`
+factor N...           the prime factors of each number
+pi N, count RANGE     how many primes there are up to N or in RANGE
+next N, prev N        the closest prime above or below N
+nth K, first K        the K-th prime, the first K primes
+list --from A --to B  the primes between A and B, both included
+help [COMMAND]        this list, or the options of COMMAND
+quit, exit            leave - Ctrl-D works too
+Numbers can be written with a power of ten, as in `pi 1e9`.";
+
+/// One line of input, understood.
+#[derive(Debug)]
+enum Action {
+    Run(Command, ErrorMode),
+    Help,
+    Quit,
+}
+
+/// Reads commands from the terminal until `quit` or the end of the input.
+///
+/// Each line is run as a one-shot `primes` command would be, so `--strict`
+/// can be given per line as well as for the whole session. Invalid lines are
+/// reported and the session goes on.
+pub fn run(mode: ErrorMode, out: &mut impl Write, err: &mut impl Write) -> io::Result<Status> {
+    let config = Config::builder()
+        .max_history_size(HISTORY_SIZE)
+        .map_err(io::Error::other)?
+        .auto_add_history(true)
+        .build();
+    let mut editor: Editor<CommandCompleter, DefaultHistory> =
+        Editor::with_config(config).map_err(io::Error::other)?;
+    editor.set_helper(Some(CommandCompleter));
+
+    let history = history_path();
+    if let Some(path) = &history {
+        // there is nothing to load before the first session
+        let _ = editor.load_history(path);
+    }
+
+    loop {
+        let line = match editor.readline(PROMPT) {
+            Ok(line) => line,
+            // Ctrl-C drops the line being typed, as in a shell
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(io::Error::other(e)),
+        };
+        let go_on = eval(&line, mode, out, err)?;
+        out.flush()?;
+        if !go_on {
+            break;
+        }
+    }
+
+    if let Some(path) = &history {
+        if let Err(e) = editor.save_history(path) {
+            writeln!(
+                err,
+                "primes: cannot save history to {}: {}",
+                path.display(),
+                e
+            )?;
+        }
+    }
+    Ok(Status::Success)
+}
+
+/// Runs one line and tells whether the session goes on.
+fn eval(
+    line: &str,
+    mode: ErrorMode,
+    out: &mut impl Write,
+    err: &mut impl Write,
+) -> io::Result<bool> {
+    if line.trim().is_empty() {
+        return Ok(true);
+    }
+    match parse_line(line, mode) {
+        Ok(Action::Run(command, mode)) => {
+            commands::run(&command, mode, out, err)?;
+        }
+        Ok(Action::Help) => writeln!(out, "{}", HELP)?,
+        Ok(Action::Quit) => return Ok(false),
+        // `help check` and `--version` are clap "errors" for stdout
+        Err(e) if e.use_stderr() => write!(err, "{}", e.render())?,
+        Err(e) => write!(out, "{}", e.render())?,
+    }
+    Ok(true)
+}
+
+fn parse_line(line: &str, mode: ErrorMode) -> Result<Action, clap::Error> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    match words[..] {
+        ["help"] => return Ok(Action::Help),
+        ["quit"] | ["exit"] => return Ok(Action::Quit),
+        [range] if range.contains("..") => {
+            let range = parse_range(range)
+                .map_err(|e| Cli::command().error(ErrorKind::ValueValidation, e))?;
+            let command = Command::List {
+                from: *range.start(),
+                to: *range.end(),
+            };
+            return Ok(Action::Run(command, mode));
+        }
+        _ => {}
+    }
+
+    let cli = Cli::try_parse_from(["primes"].into_iter().chain(words))?;
+    // the session mode holds unless the line asks for strict
+    let mode = match cli.error_mode() {
+        ErrorMode::Strict => ErrorMode::Strict,
+        ErrorMode::KeepGoing => mode,
+    };
+    match cli.into_command()? {
+        Command::Repl => Err(Cli::command().error(
+            ErrorKind::InvalidSubcommand,
+            "this is already an interactive session",
+        )),
+        command => Ok(Action::Run(command, mode)),
+    }
+}
+
+fn history_path() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .filter(|home| !home.is_empty())
+        .map(|home| PathBuf::from(home).join(HISTORY_FILE))
+}
+
+/// The commands starting with the word under the cursor, if it is the first
+/// one, and where that word starts.
+fn complete_command(line: &str, pos: usize) -> (usize, Vec<String>) {
+    let before = &line[..pos];
+    let start = before.len() - before.trim_start().len();
+    let word = &before[start..];
+    if word.contains(char::is_whitespace) {
+        return (pos, Vec::new());
+    }
+    let candidates = COMMANDS
+        .iter()
+        .filter(|command| command.starts_with(word))
+        .map(|command| command.to_string())
+        .collect();
+    (start, candidates)
+}
+
+struct CommandCompleter;
+
+impl Completer for CommandCompleter {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        Ok(complete_command(line, pos))
+    }
+}
+
+impl Hinter for CommandCompleter {
+    type Hint = String;
+}
+
+impl Highlighter for CommandCompleter {}
+
+impl Validator for CommandCompleter {}
+
+impl Helper for CommandCompleter {}
+
+#[cfg(test)]
+mod tests_repl {
+    use crate::cli::ErrorMode;
+    use crate::repl::{complete_command, eval};
+
+    fn eval_line(line: &str) -> (bool, String, String) {
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        let go_on = eval(line, ErrorMode::KeepGoing, &mut out, &mut err).unwrap();
+        (
+            go_on,
+            String::from_utf8(out).unwrap(),
+            String::from_utf8(err).unwrap(),
+        )
+    }
+
+    fn output(line: &str) -> String {
+        let (go_on, out, err) = eval_line(line);
+        assert!(go_on);
+        assert_eq!(err, "", "{}", line);
+        out
+    }
+
+    #[test]
+    fn test_numbers_and_ranges() {
+        assert_eq!(output("97"), "97 is prime\n");
+        assert_eq!(output(" 91 2 "), "91 is not prime\n2 is prime\n");
+        assert_eq!(output("100..120"), "101\n103\n107\n109\n113\n");
+        assert_eq!(output("..=7"), "2\n3\n5\n7\n");
+        assert_eq!(output("10..10"), "");
+        assert_eq!(output(""), "");
+    }
+
+    #[test]
+    fn test_commands() {
+        assert_eq!(output("factor 91"), "91: 7 13\n");
+        assert_eq!(output("pi 1e6"), "78498\n");
+        assert_eq!(output("count 100..200"), "21\n");
+        assert_eq!(output("next 1000"), "1009\n");
+        assert_eq!(output("prev 1000"), "997\n");
+        assert_eq!(output("nth 100"), "541\n");
+        assert_eq!(
+            output("check --format csv 4"),
+            "input,value,prime,error\n4,4,false,\n"
+        );
+        assert!(output("help").contains("pi N"));
+        assert!(output("help factor").contains("Usage: primes factor"));
+    }
+
+    #[test]
+    fn test_quit() {
+        assert!(!eval_line("quit").0);
+        assert!(!eval_line(" exit ").0);
+    }
+
+    #[test]
+    fn test_errors_do_not_end_the_session() {
+        let (go_on, out, err) = eval_line("7 x");
+        assert!(go_on);
+        assert_eq!(out, "7 is prime\n");
+        assert_eq!(err, "primes: invalid number \"x\": not a number\n");
+
+        let (go_on, out, err) = eval_line("--strict 7 x");
+        assert!(go_on);
+        assert_eq!(out, "");
+        assert_eq!(err, "primes: invalid number \"x\": not a number\n");
+
+        for line in ["1..x", "nth 0", "factor", "list --to", "repl"] {
+            let (go_on, out, err) = eval_line(line);
+            assert!(go_on, "{}", line);
+            assert_eq!(out, "", "{}", line);
+            assert!(err.starts_with("error: "), "{}: {}", line, err);
+        }
+
+        let (_, _, err) = eval_line("prev 2");
+        assert_eq!(err, "primes: no prime below 2\n");
+    }
+
+    #[test]
+    fn test_completion() {
+        assert_eq!(complete_command("fa", 2), (0, vec!["factor".to_string()]));
+        assert_eq!(
+            complete_command("  n", 3),
+            (2, vec!["next".to_string(), "nth".to_string()])
+        );
+        assert_eq!(complete_command("", 0).1.len(), 12);
+        assert_eq!(complete_command("factor 9", 8), (8, Vec::new()));
+        assert_eq!(complete_command("zz", 2), (0, Vec::new()));
+    }
+}
@@ -0,0 +1,381 @@
+use std::io::{self, BufRead, Read, Write};
+use std::num::NonZeroUsize;
+use std::thread;
+
+use crate::cli::{ErrorMode, Output};
+use crate::commands::{Status, Tally};
+use crate::format::{Details, Record, RecordWriter};
+use crate::input::{parse_number, InputError};
+
+/// Lines read and checked at once - the memory use does not grow with the input.
+const CHUNK_LINES: usize = 1 << 14;
+
+/// Longest line kept, newline included - no number is anywhere near as long.
+const MAX_LINE_BYTES: usize = 1024;
+
+/// One line of input, cut at [`MAX_LINE_BYTES`].
+struct Line {
+    text: String,
+    too_long: bool,
+}
+
+/// Checks one number per line of `input`, printing the records in input order.
+///
+/// Lines are read in chunks of [`CHUNK_LINES`], and each chunk is split between
+/// `threads` threads. Blank lines are skipped. Invalid lines, including those
+/// longer than [`MAX_LINE_BYTES`], are reported on `err` with their line
+/// number; in strict mode the first one ends the stream. A read error ends the
+/// stream too, after the lines read before it. The tally goes to `err` at the
+/// end, so that `out` only holds records.
+pub fn check_lines(
+    mut input: impl BufRead,
+    threads: usize,
+    output: &Output,
+    mode: ErrorMode,
+    out: &mut impl Write,
+    err: &mut impl Write,
+) -> io::Result<Status> {
+    let mut writer = RecordWriter::begin(output.format, output.details(), out)?;
+    let mut tally = Tally::default();
+    let mut line_number = 0u64;
+    let mut chunk = Vec::with_capacity(CHUNK_LINES);
+    let mut buffer = Vec::new();
+    let mut read_error = None;
+
+    'chunks: loop {
+        chunk.clear();
+        while chunk.len() < CHUNK_LINES {
+            match read_line(&mut input, &mut buffer) {
+                Ok(Some(line)) => chunk.push(line),
+                Ok(None) => break,
+                Err(e) => {
+                    read_error = Some((line_number + 1, e));
+                    break;
+                }
+            }
+            line_number += 1;
+        }
+        if chunk.is_empty() {
+            break;
+        }
+
+        let first_line = line_number - chunk.len() as u64 + 1;
+        let records = check_chunk(&chunk, threads, output.details());
+        for (i, record) in records.iter().enumerate() {
+            // blank lines have no record
+            let Some(record) = record else {
+                continue;
+            };
+            tally.add(record);
+            writer.write(record, out)?;
+            if let Err(e) = record.value {
+                writeln!(
+                    err,
+                    "primes: line {}: invalid number {:?}: {}",
+                    first_line + i as u64,
+                    record.input,
+                    e
+                )?;
+                if mode == ErrorMode::Strict {
+                    // the stream ends before the line that could not be read
+                    read_error = None;
+                    break 'chunks;
+                }
+            }
+        }
+
+        // a pipeline downstream sees each chunk as soon as it is done
+        out.flush()?;
+        if chunk.len() < CHUNK_LINES {
+            break;
+        }
+    }
+
+    if let Some((line, e)) = &read_error {
+        writeln!(err, "primes: cannot read line {}: {}", line, e)?;
+    }
+    writer.finish(out)?;
+    writeln!(
+        err,
+        "primes: {} prime, {} not prime, {} invalid",
+        tally.primes, tally.not_primes, tally.invalid
+    )?;
+    match read_error {
+        Some(_) => Ok(Status::ReadFailed),
+        None => Ok(tally.status()),
+    }
+}
+
+/// Reads the next line, or `None` at the end of the input.
+///
+/// Only the first [`MAX_LINE_BYTES`] of a longer line are kept; the rest is
+/// skipped up to the next newline, so a file without any cannot fill memory.
+fn read_line(input: &mut impl BufRead, buffer: &mut Vec<u8>) -> io::Result<Option<Line>> {
+    buffer.clear();
+    let limit = MAX_LINE_BYTES as u64 + 1;
+    if Read::take(&mut *input, limit).read_until(b'\n', buffer)? == 0 {
+        return Ok(None);
+    }
+
+    let too_long = buffer.len() > MAX_LINE_BYTES && buffer.last() != Some(&b'\n');
+    if too_long {
+        buffer.truncate(MAX_LINE_BYTES);
+        skip_line(input)?;
+    }
+    Ok(Some(Line {
+        text: String::from_utf8_lossy(buffer).into_owned(),
+        too_long,
+    }))
+}
+
+/// Drops the input up to and including the next newline.
+fn skip_line(input: &mut impl BufRead) -> io::Result<()> {
+    loop {
+        let available = input.fill_buf()?;
+        if available.is_empty() {
+            return Ok(());
+        }
+        match available.iter().position(|&b| b == b'\n') {
+            Some(end) => {
+                input.consume(end + 1);
+                return Ok(());
+            }
+            None => {
+                let len = available.len();
+                input.consume(len);
+            }
+        }
+    }
+}
+
+/// Number of threads for [`check_lines`] - one per available core.
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+fn check_chunk(lines: &[Line], threads: usize, details: Details) -> Vec<Option<Record>> {
+    let per_thread = lines.len().div_ceil(threads.max(1)).max(1);
+    thread::scope(|scope| {
+        let workers: Vec<_> = lines
+            .chunks(per_thread)
+            .map(|part| {
+                scope.spawn(move || {
+                    part.iter()
+                        .map(|line| line_record(line, details))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap())
+            .collect()
+    })
+}
+
+fn line_record(line: &Line, details: Details) -> Option<Record> {
+    if line.too_long {
+        // whatever the rest holds, the line is no number the commands take
+        let error = parse_number(&line.text)
+            .err()
+            .unwrap_or(InputError::NotANumber);
+        return Some(Record {
+            input: line.text.clone(),
+            value: Err(error),
+            prime: false,
+            factors: None,
+            neighbors: None,
+        });
+    }
+
+    let text = line.text.trim_end_matches(['\n', '\r']);
+    if text.trim().is_empty() {
+        return None;
+    }
+    Some(Record::new(text, details))
+}
+
+#[cfg(test)]
+mod tests_stream {
+    use crate::cli::{ErrorMode, Output};
+    use crate::commands::Status;
+    use crate::format::Format;
+    use crate::input::InputError;
+    use crate::stream::{check_lines, CHUNK_LINES, MAX_LINE_BYTES};
+    use primes_lib::math_utils::is_prime;
+    use std::io::{self, BufReader, Cursor, Read};
+
+    fn check(input: &str, threads: usize, mode: ErrorMode) -> (Status, String, String) {
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        let status = check_lines(
+            Cursor::new(input),
+            threads,
+            &Output::default(),
+            mode,
+            &mut out,
+            &mut err,
+        )
+        .unwrap();
+        (
+            status,
+            String::from_utf8(out).unwrap(),
+            String::from_utf8(err).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_numbers_file() {
+        // the contents of notebooks/numbers.txt
+        let (status, out, err) = check("1\n2\n3\n42\nt665\n6\n7\n8", 2, ErrorMode::KeepGoing);
+        assert_eq!(status, Status::InvalidInput(InputError::NotANumber));
+        assert_eq!(
+            out,
+            "1 is not prime\n2 is prime\n3 is prime\n42 is not prime\n\
+             6 is not prime\n7 is prime\n8 is not prime\n"
+        );
+        assert_eq!(
+            err,
+            "primes: line 5: invalid number \"t665\": not a number\n\
+             primes: 3 prime, 4 not prime, 1 invalid\n"
+        );
+    }
+
+    #[test]
+    fn test_blank_lines_and_line_endings() {
+        let (status, out, err) = check("\n 7 \r\n\n\r\n11\r\n", 1, ErrorMode::KeepGoing);
+        assert_eq!(status, Status::Success);
+        assert_eq!(out, "7 is prime\n11 is prime\n");
+        assert_eq!(err, "primes: 2 prime, 0 not prime, 0 invalid\n");
+
+        let (status, out, _) = check("", 4, ErrorMode::KeepGoing);
+        assert_eq!(status, Status::Success);
+        assert_eq!(out, "");
+    }
+
+    #[test]
+    fn test_strict_stops_at_first_invalid_line() {
+        let (status, out, err) = check("7\n8\n-9\n11\nx\n", 3, ErrorMode::Strict);
+        assert_eq!(status, Status::InvalidInput(InputError::Negative));
+        assert_eq!(out, "7 is prime\n8 is not prime\n");
+        assert_eq!(
+            err,
+            "primes: line 3: invalid number \"-9\": negative numbers are not supported\n\
+             primes: 1 prime, 1 not prime, 1 invalid\n"
+        );
+    }
+
+    #[test]
+    fn test_order_is_kept_across_chunks_and_threads() {
+        let count = 2 * CHUNK_LINES as u64 + 123;
+        let input: String = (0..count)
+            .map(|i| format!("{}\n", i * 7_919 % 100_003))
+            .collect();
+        let expected: String = (0..count)
+            .map(|i| {
+                let n = i * 7_919 % 100_003;
+                let verdict = if is_prime(n) {
+                    "is prime"
+                } else {
+                    "is not prime"
+                };
+                format!("{} {}\n", n, verdict)
+            })
+            .collect();
+
+        for threads in [1, 3, 8] {
+            let (status, out, err) = check(&input, threads, ErrorMode::KeepGoing);
+            assert_eq!(status, Status::NotPrime);
+            assert_eq!(out, expected, "{} threads", threads);
+            assert!(err.ends_with(" invalid\n") && !err.contains("invalid number"));
+        }
+    }
+
+    #[test]
+    fn test_json_stream_stays_valid_after_strict_stop() {
+        let output = Output {
+            format: Format::Json,
+            ..Output::default()
+        };
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        let input = Cursor::new("7\n\nx\n9\n");
+        let status = check_lines(input, 2, &output, ErrorMode::Strict, &mut out, &mut err).unwrap();
+
+        assert_eq!(status, Status::InvalidInput(InputError::NotANumber));
+        let records: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(records[0]["value"], 7);
+        assert_eq!(records[1]["input"], "x");
+        assert_eq!(records.as_array().unwrap().len(), 2);
+    }
+
+    struct FailingReader;
+
+    impl Read for FailingReader {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("device gone"))
+        }
+    }
+
+    #[test]
+    fn test_read_error() {
+        let input = BufReader::new(Cursor::new("7\n").chain(FailingReader));
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        let status = check_lines(
+            input,
+            2,
+            &Output::default(),
+            ErrorMode::KeepGoing,
+            &mut out,
+            &mut err,
+        )
+        .unwrap();
+        assert_eq!(status, Status::ReadFailed);
+        assert_eq!(String::from_utf8(out).unwrap(), "7 is prime\n");
+        assert_eq!(
+            String::from_utf8(err).unwrap(),
+            "primes: cannot read line 2: device gone\n\
+             primes: 1 prime, 0 not prime, 0 invalid\n"
+        );
+    }
+
+    #[test]
+    fn test_json_stream_stays_valid_after_read_error() {
+        let output = Output {
+            format: Format::Json,
+            ..Output::default()
+        };
+        let input = BufReader::new(Cursor::new("7\n8\n").chain(FailingReader));
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        let status =
+            check_lines(input, 2, &output, ErrorMode::KeepGoing, &mut out, &mut err).unwrap();
+
+        assert_eq!(status, Status::ReadFailed);
+        let records: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(records[0]["value"], 7);
+        assert_eq!(records[1]["value"], 8);
+        assert_eq!(records.as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_long_lines_are_invalid() {
+        let digits = "1".repeat(3 * MAX_LINE_BYTES);
+        let padded = format!("7{}", " ".repeat(MAX_LINE_BYTES));
+        let input = format!("{}\n{}\n11\n{}", digits, padded, "x".repeat(5_000));
+        let (status, out, err) = check(&input, 2, ErrorMode::KeepGoing);
+
+        assert_eq!(status, Status::InvalidInput(InputError::Overflow));
+        assert_eq!(out, "11 is prime\n");
+        let lines: Vec<&str> = err.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("primes: line 1: invalid number \"111"));
+        assert!(lines[0].ends_with(&format!("\": larger than {}", u64::MAX)));
+        assert!(lines[1].starts_with("primes: line 2: invalid number \"7 "));
+        assert!(lines[1].ends_with("\": not a number"));
+        assert!(lines[2].starts_with("primes: line 4: invalid number \"xxx"));
+        assert_eq!(lines[3], "primes: 1 prime, 0 not prime, 3 invalid");
+
+        // a line cut exactly at the limit still counts as one line
+        let exact = format!("{:>width$}\n13\n", 7, width = MAX_LINE_BYTES - 1);
+        let (_, out, _) = check(&exact, 1, ErrorMode::KeepGoing);
+        assert_eq!(out, "7 is prime\n13 is prime\n");
+    }
+}
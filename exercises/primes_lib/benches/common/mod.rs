@@ -0,0 +1,53 @@
+#![allow(dead_code)]
+
+use primes_lib::math_utils::is_prime;
+
+/// Fixed linear congruential generator - every run benchmarks the same inputs.
+pub struct Lcg(u64);
+
+impl Lcg {
+    pub fn new() -> Lcg {
+        Lcg(0x2545_F491_4F6C_DD1D)
+    }
+
+    pub fn next(&mut self) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        self.0
+    }
+
+    /// Uniform-ish value in `low..high`.
+    pub fn in_range(&mut self, low: u64, high: u64) -> u64 {
+        low + self.next() % (high - low)
+    }
+}
+
+/// `count` odd values in `low..high`, every other one prime - the worst case
+/// for trial division next to typical composites.
+pub fn mixed_values(low: u64, high: u64, count: usize) -> Vec<u64> {
+    let mut rng = Lcg::new();
+    let mut values = Vec::with_capacity(count);
+
+    while values.len() < count {
+        let n = rng.in_range(low, high) | 1;
+        if values.len() % 2 == 1 || is_prime(n) {
+            values.push(n);
+        }
+    }
+    values
+}
+
+/// `count` uniformly drawn values in `low..high`.
+pub fn random_values(low: u64, high: u64, count: usize) -> Vec<u64> {
+    let mut rng = Lcg::new();
+    (0..count).map(|_| rng.in_range(low, high)).collect()
+}
+
+/// Input size classes shared by the benchmarks: `(name, low, high)`.
+pub const SIZE_CLASSES: [(&str, u64, u64); 3] = [
+    ("small", 1 << 8, 1 << 16),
+    ("medium", 1 << 20, 1 << 32),
+    ("64-bit", 1 << 62, u64::MAX),
+];
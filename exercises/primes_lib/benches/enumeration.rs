@@ -0,0 +1,89 @@
+//! Listing primes: `n_primes`, `primes_in_range` on dense and sparse ranges,
+//! `primes_from_vec`, and the sieves and iterators behind them.
+//!
+//! Run with `cargo bench -p primes_lib --bench enumeration`.
+
+mod common;
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use primes_lib::math_utils::{n_primes, primes_from_vec, primes_in_range, Primes};
+use primes_lib::parallel::{par_primes_from_vec, par_primes_in_range};
+use primes_lib::sieve::{sieve_range, LinearSieve};
+
+use common::SIZE_CLASSES;
+
+/// Threads used by the parallel variants.
+const THREADS: u32 = 4;
+
+fn bench_n_primes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("n_primes");
+    group.sample_size(20);
+
+    for count in [1_000, 100_000, 1_000_000] {
+        group.bench_function(BenchmarkId::from_parameter(count), |b| {
+            b.iter(|| n_primes::<u64>(black_box(count)))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_ranges(c: &mut Criterion) {
+    let mut group = c.benchmark_group("primes_in_range");
+    group.sample_size(20);
+
+    // dense: wide windows that are sieved; sparse: narrow windows high up where
+    // every candidate is tested on its own
+    let ranges = [
+        ("dense 0..10^6", 0, 1_000_000),
+        ("dense 10^12 + 10^6", 1_000_000_000_000, 1_000_001_000_000),
+        ("sparse 10^12 + 10^3", 1_000_000_000_000, 1_000_000_001_000),
+        ("sparse 2^64 - 10^4", u64::MAX - 10_000, u64::MAX),
+    ];
+
+    for (name, start, end) in ranges {
+        group.bench_function(BenchmarkId::new("primes_in_range", name), |b| {
+            b.iter(|| primes_in_range(black_box(start)..black_box(end)))
+        });
+        group.bench_function(BenchmarkId::new("par_primes_in_range", name), |b| {
+            b.iter(|| par_primes_in_range(black_box(start)..black_box(end), THREADS))
+        });
+        group.bench_function(BenchmarkId::new("Primes iterator", name), |b| {
+            b.iter(|| Primes::in_range(black_box(start)..black_box(end)).count())
+        });
+    }
+
+    for (name, start, end) in &ranges[..2] {
+        group.bench_function(BenchmarkId::new("sieve_range", name), |b| {
+            b.iter(|| sieve_range(black_box(*start)..black_box(*end)))
+        });
+    }
+    group.bench_function(BenchmarkId::new("LinearSieve", "0..10^6"), |b| {
+        b.iter(|| LinearSieve::new(black_box(1_000_000)))
+    });
+
+    group.finish();
+}
+
+fn bench_from_vec(c: &mut Criterion) {
+    let mut group = c.benchmark_group("primes_from_vec");
+    group.sample_size(20);
+
+    for (name, low, high) in SIZE_CLASSES {
+        let values = common::random_values(low, high, 10_000);
+
+        group.bench_function(BenchmarkId::new("primes_from_vec", name), |b| {
+            b.iter(|| primes_from_vec(black_box(values.clone())))
+        });
+        group.bench_function(BenchmarkId::new("par_primes_from_vec", name), |b| {
+            b.iter(|| par_primes_from_vec(black_box(values.clone()), THREADS))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_n_primes, bench_ranges, bench_from_vec);
+criterion_main!(benches);
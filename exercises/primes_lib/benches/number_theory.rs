@@ -0,0 +1,117 @@
+//! Counting, factoring and the other algorithms built on top of the sieves.
+//!
+//! Run with `cargo bench -p primes_lib --bench number_theory`.
+
+mod common;
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use primes_lib::arith::euler_phi_table;
+use primes_lib::certificate::{certify, verify_certificate};
+use primes_lib::constellations::{maximal_gaps, twin_primes};
+use primes_lib::counting::{nth_prime, prime_pi};
+use primes_lib::factor::factorize;
+use primes_lib::prime_set::PrimeSet;
+
+use common::SIZE_CLASSES;
+
+fn bench_counting(c: &mut Criterion) {
+    let mut group = c.benchmark_group("counting");
+    group.sample_size(10);
+
+    for x in [1_000_000u64, 10_000_000_000] {
+        group.bench_function(BenchmarkId::new("prime_pi", x), |b| {
+            b.iter(|| prime_pi(black_box(x)))
+        });
+    }
+    for n in [10_000u64, 10_000_000] {
+        group.bench_function(BenchmarkId::new("nth_prime", n), |b| {
+            b.iter(|| nth_prime(black_box(n)))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_factorize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("factorize");
+
+    for (name, low, high) in SIZE_CLASSES {
+        let values = common::random_values(low, high, 32);
+        group.bench_function(BenchmarkId::from_parameter(name), |b| {
+            b.iter(|| {
+                values
+                    .iter()
+                    .map(|&n| factorize(black_box(n)).len())
+                    .sum::<usize>()
+            })
+        });
+    }
+
+    // two 32-bit prime factors - the hardest case for Pollard's rho
+    let semiprime = 4_294_967_291u64 * 4_294_967_279;
+    group.bench_function("semiprime 64-bit", |b| {
+        b.iter(|| factorize(black_box(semiprime)))
+    });
+
+    group.finish();
+}
+
+fn bench_prime_set(c: &mut Criterion) {
+    let mut group = c.benchmark_group("PrimeSet");
+    group.sample_size(20);
+
+    group.bench_function("new 10^7", |b| {
+        b.iter(|| PrimeSet::new(black_box(10_000_000)))
+    });
+
+    let set = PrimeSet::new(10_000_000);
+    let values = common::random_values(0, 10_000_000, 1_000);
+    group.bench_function("rank", |b| {
+        b.iter(|| values.iter().map(|&n| set.rank(black_box(n))).sum::<u64>())
+    });
+    group.bench_function("select", |b| {
+        b.iter(|| {
+            values
+                .iter()
+                .filter_map(|&k| set.select(black_box(k % 600_000 + 1)))
+                .sum::<u64>()
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_misc(c: &mut Criterion) {
+    let mut group = c.benchmark_group("misc");
+    group.sample_size(20);
+
+    group.bench_function("twin_primes 0..10^6", |b| {
+        b.iter(|| twin_primes(black_box(0)..black_box(1_000_000)).count())
+    });
+    group.bench_function("maximal_gaps 0..10^6", |b| {
+        b.iter(|| maximal_gaps(black_box(0)..black_box(1_000_000)).count())
+    });
+    group.bench_function("euler_phi_table 10^6", |b| {
+        b.iter(|| euler_phi_table(black_box(1_000_000)))
+    });
+
+    let prime = 18_446_744_073_709_551_557;
+    let certificate = certify(prime).unwrap();
+    group.bench_function("certify 64-bit", |b| b.iter(|| certify(black_box(prime))));
+    group.bench_function("verify_certificate 64-bit", |b| {
+        b.iter(|| verify_certificate(black_box(&certificate)))
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_counting,
+    bench_factorize,
+    bench_prime_set,
+    bench_misc
+);
+criterion_main!(benches);
@@ -0,0 +1,140 @@
+//! Single-number primality tests - every variant on the input sizes it supports.
+//!
+//! Run with `cargo bench -p primes_lib --bench primality`.
+
+mod common;
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use primes_lib::big_primes::{is_probable_prime, miller_rabin};
+use primes_lib::bigint::BigUint;
+use primes_lib::math_utils::{is_prime, is_prime_fast, is_prime_trial};
+use primes_lib::montgomery::is_prime_u128;
+use primes_lib::prime_set::PrimeSet;
+use primes_lib::random::Xoshiro256;
+use primes_lib::special::{llr, lucas_lehmer, pepin, proth};
+
+use common::SIZE_CLASSES;
+
+fn bench_u64_tests(c: &mut Criterion) {
+    let table = PrimeSet::new(1 << 24);
+
+    for (name, low, high) in SIZE_CLASSES {
+        let values = common::mixed_values(low, high, 64);
+        let mut group = c.benchmark_group(format!("is_prime {}", name));
+
+        group.bench_function("is_prime", |b| {
+            b.iter(|| values.iter().filter(|&&n| is_prime(black_box(n))).count())
+        });
+        group.bench_function("is_prime_fast", |b| {
+            b.iter(|| {
+                values
+                    .iter()
+                    .filter(|&&n| is_prime_fast(black_box(n)))
+                    .count()
+            })
+        });
+        group.bench_function("is_prime_u128", |b| {
+            b.iter(|| {
+                values
+                    .iter()
+                    .filter(|&&n| is_prime_u128(black_box(n as u128)))
+                    .count()
+            })
+        });
+        if high <= 1 << 32 {
+            group.bench_function("is_prime_trial", |b| {
+                b.iter(|| {
+                    values
+                        .iter()
+                        .filter(|&&n| is_prime_trial(black_box(n)))
+                        .count()
+                })
+            });
+        }
+        if high <= 1 << 24 {
+            group.bench_function("PrimeSet::contains", |b| {
+                b.iter(|| {
+                    values
+                        .iter()
+                        .filter(|&&n| table.contains(black_box(n)))
+                        .count()
+                })
+            });
+        }
+
+        group.finish();
+    }
+}
+
+fn bench_wide_tests(c: &mut Criterion) {
+    let mut group = c.benchmark_group("is_prime wide");
+
+    // the largest prime below 2^128 and the composite right above it
+    let wide = [u128::MAX - 158, u128::MAX - 157];
+    group.bench_function("is_prime_u128 128-bit", |b| {
+        b.iter(|| {
+            wide.iter()
+                .filter(|&&n| is_prime_u128(black_box(n)))
+                .count()
+        })
+    });
+
+    let mut rng = Xoshiro256::seed_from_u64(1);
+    for bits in [128, 512, 2048] {
+        let prime = primes_lib::big_primes::random_prime(bits, &mut rng);
+        let composite = &prime * &primes_lib::big_primes::random_prime(bits / 2, &mut rng);
+
+        group.bench_function(BenchmarkId::new("BPSW prime", bits), |b| {
+            b.iter(|| is_probable_prime(black_box(&prime)))
+        });
+        group.bench_function(BenchmarkId::new("BPSW composite", bits), |b| {
+            b.iter(|| is_probable_prime(black_box(&composite)))
+        });
+        group.bench_function(BenchmarkId::new("Miller-Rabin 10 rounds", bits), |b| {
+            let mut rng = Xoshiro256::seed_from_u64(2);
+            b.iter(|| miller_rabin(black_box(&prime), 10, &mut rng))
+        });
+    }
+
+    let base = BigUint::from(3u64);
+    let modulus = primes_lib::big_primes::random_prime(1024, &mut rng);
+    let exp = &modulus - 1;
+    group.bench_function("BigUint::pow_mod 1024-bit", |b| {
+        b.iter(|| black_box(&base).pow_mod(&exp, &modulus))
+    });
+
+    group.finish();
+}
+
+fn bench_special_forms(c: &mut Criterion) {
+    let mut group = c.benchmark_group("special forms");
+    group.sample_size(10);
+
+    group.bench_function("lucas_lehmer 2^4423 - 1", |b| {
+        b.iter(|| lucas_lehmer(black_box(4_423)))
+    });
+    group.bench_function("pepin F_12", |b| b.iter(|| pepin(black_box(12))));
+    group.bench_function("proth 3 * 2^534 + 1", |b| {
+        b.iter(|| proth(black_box(3), black_box(534)))
+    });
+    group.bench_function("llr 3 * 2^470 - 1", |b| {
+        b.iter(|| llr(black_box(3), black_box(470)))
+    });
+
+    let square = BigUint::from(3u64).pow(40_000);
+    group.bench_function("BigUint::square 63400-bit", |b| {
+        b.iter(|| black_box(&square).square())
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_u64_tests,
+    bench_wide_tests,
+    bench_special_forms
+);
+criterion_main!(benches);
@@ -0,0 +1,48 @@
+//! Trial division before and after the wheel rewrite, one group per input size.
+//!
+//! Run with `cargo bench -p primes_lib --bench trial_division`.
+
+mod common;
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use primes_lib::math_utils::{is_prime, is_prime_trial, is_prime_trial_naive};
+
+fn bench_trial_division(c: &mut Criterion) {
+    let classes = [
+        ("below 2^16", 1 << 8, 1 << 16),
+        ("2^16 to 2^20", 1 << 16, 1 << 20),
+        ("2^20 to 2^32", 1 << 20, 1 << 32),
+    ];
+
+    for (name, low, high) in classes {
+        let values = common::mixed_values(low, high, 64);
+        let mut group = c.benchmark_group(format!("trial division {}", name));
+
+        group.bench_function("naive", |b| {
+            b.iter(|| {
+                values
+                    .iter()
+                    .filter(|&&n| is_prime_trial_naive(black_box(n)))
+                    .count()
+            })
+        });
+        group.bench_function("wheel", |b| {
+            b.iter(|| {
+                values
+                    .iter()
+                    .filter(|&&n| is_prime_trial(black_box(n)))
+                    .count()
+            })
+        });
+        group.bench_function("is_prime", |b| {
+            b.iter(|| values.iter().filter(|&&n| is_prime(black_box(n))).count())
+        });
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_trial_division);
+criterion_main!(benches);
@@ -0,0 +1,69 @@
+#![no_main]
+
+//! Runs every primality test in the crate on the same `u128` and fails on any
+//! disagreement. The `BigUint` tests are called without their shortcut for
+//! values that fit in `u64`, so each algorithm really runs. Start it from
+//! `exercises/primes_lib` with `cargo +nightly fuzz run primality`.
+
+use libfuzzer_sys::fuzz_target;
+use primes_lib::big_primes::{baillie_psw, is_probable_prime, miller_rabin_rounds};
+use primes_lib::bigint::BigUint;
+use primes_lib::certificate::{certify, verify_certificate};
+use primes_lib::factor::factorize;
+use primes_lib::math_utils::{is_prime, is_prime_fast, is_prime_trial};
+use primes_lib::montgomery::is_prime_u128;
+use primes_lib::random::Xoshiro256;
+
+/// Trial division is only run below this bound to keep the executions fast.
+const TRIAL_DIVISION_LIMIT: u64 = 1 << 32;
+
+/// Random Miller-Rabin bases - a composite survives with probability below 4^-32,
+/// so a failure is a bug rather than bad luck.
+const MILLER_RABIN_ROUNDS: u32 = 32;
+
+fuzz_target!(|n: u128| {
+    let small = u64::try_from(n).ok();
+    // deterministic Miller-Rabin below 2^64, Montgomery Baillie-PSW above
+    let expected = small.map_or_else(|| is_prime_u128(n), is_prime_fast);
+    let big = BigUint::from(n);
+
+    assert_eq!(is_prime(n), expected, "is_prime({})", n);
+    assert_eq!(is_prime_u128(n), expected, "is_prime_u128({})", n);
+    assert_eq!(baillie_psw(&big), expected, "baillie_psw({})", n);
+    assert_eq!(
+        is_probable_prime(&big),
+        expected,
+        "is_probable_prime({})",
+        n
+    );
+    let mut rng = Xoshiro256::seed_from_u64(n as u64 ^ (n >> 64) as u64);
+    assert_eq!(
+        miller_rabin_rounds(&big, MILLER_RABIN_ROUNDS, &mut rng),
+        expected,
+        "miller_rabin_rounds({})",
+        n
+    );
+
+    let Some(n) = small else {
+        return;
+    };
+    if n < TRIAL_DIVISION_LIMIT {
+        assert_eq!(is_prime_trial(n), expected, "is_prime_trial({})", n);
+    }
+
+    let factors = factorize(n);
+    let is_single_factor = factors.len() == 1 && factors[0] == (n, 1);
+    assert_eq!(
+        is_single_factor, expected,
+        "factorize({}) = {:?}",
+        n, factors
+    );
+
+    match certify(n) {
+        Some(certificate) => {
+            assert!(expected, "certify({}) succeeded for a composite", n);
+            assert!(verify_certificate(&certificate), "certificate for {}", n);
+        }
+        None => assert!(!expected, "certify({}) failed for a prime", n),
+    }
+});
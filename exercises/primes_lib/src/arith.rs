@@ -0,0 +1,204 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::factor::factorize;
+use crate::modular::lcm;
+use crate::sieve::LinearSieve;
+
+/// Euler's totient - how many of `1..=n` are coprime to `n`. `euler_phi(0) == 0`.
+pub fn euler_phi(n: u64) -> u64 {
+    if n == 0 {
+        return 0;
+    }
+    factorize(n)
+        .into_iter()
+        .map(|(p, e)| phi_prime_power(p, e))
+        .product()
+}
+
+/// Möbius function - `0` if `n` has a squared prime factor, otherwise `(-1)^k` for
+/// `k` distinct prime factors. `mobius(0) == 0`.
+pub fn mobius(n: u64) -> i8 {
+    if n == 0 {
+        return 0;
+    }
+    factorize(n)
+        .into_iter()
+        .map(|(_, e)| mobius_prime_power(e))
+        .product()
+}
+
+/// Number of divisors of `n`. `divisor_count(0) == 0`.
+pub fn divisor_count(n: u64) -> u64 {
+    if n == 0 {
+        return 0;
+    }
+    factorize(n)
+        .into_iter()
+        .map(|(_, e)| e as u64 + 1)
+        .product()
+}
+
+/// Sum of the `k`-th powers of the divisors of `n` (sigma_k). `divisor_sum(0, k) == 0`.
+///
+/// # Panics
+///
+/// Panics if the result does not fit in `u128`.
+pub fn divisor_sum(n: u64, k: u32) -> u128 {
+    if n == 0 {
+        return 0;
+    }
+    factorize(n)
+        .into_iter()
+        .map(|(p, e)| sigma_prime_power(p, e, k))
+        .fold(1u128, |acc, s| {
+            acc.checked_mul(s).expect("divisor sum overflows u128")
+        })
+}
+
+/// Carmichael function - the exponent of the multiplicative group modulo `n`,
+/// i.e. the smallest `m` with `a^m = 1 (mod n)` for every `a` coprime to `n`.
+/// `carmichael_lambda(0) == 0`.
+pub fn carmichael_lambda(n: u64) -> u64 {
+    if n == 0 {
+        return 0;
+    }
+    factorize(n)
+        .into_iter()
+        .map(|(p, e)| lambda_prime_power(p, e))
+        .fold(1, lcm)
+}
+
+/// [`euler_phi`] for every value in `0..=limit`.
+///
+/// # Panics
+///
+/// Panics if `limit` does not fit in `u32`.
+pub fn euler_phi_table(limit: usize) -> Vec<u64> {
+    multiplicative_table(limit, 0, 1, |a, b| a * b, phi_prime_power)
+}
+
+/// [`mobius`] for every value in `0..=limit`.
+///
+/// # Panics
+///
+/// Panics if `limit` does not fit in `u32`.
+pub fn mobius_table(limit: usize) -> Vec<i8> {
+    multiplicative_table(limit, 0, 1, |a, b| a * b, |_, e| mobius_prime_power(e))
+}
+
+/// [`divisor_count`] for every value in `0..=limit`.
+///
+/// # Panics
+///
+/// Panics if `limit` does not fit in `u32`.
+pub fn divisor_count_table(limit: usize) -> Vec<u64> {
+    multiplicative_table(limit, 0, 1, |a, b| a * b, |_, e| e as u64 + 1)
+}
+
+/// [`divisor_sum`] for every value in `0..=limit`.
+///
+/// # Panics
+///
+/// Panics if `limit` does not fit in `u32`, or if one of the sums does not fit
+/// in `u128`.
+pub fn divisor_sum_table(limit: usize, k: u32) -> Vec<u128> {
+    multiplicative_table(
+        limit,
+        0,
+        1,
+        |a, b| a.checked_mul(b).expect("divisor sum overflows u128"),
+        |p, e| sigma_prime_power(p, e, k),
+    )
+}
+
+/// [`carmichael_lambda`] for every value in `0..=limit`.
+///
+/// # Panics
+///
+/// Panics if `limit` does not fit in `u32`.
+pub fn carmichael_lambda_table(limit: usize) -> Vec<u64> {
+    multiplicative_table(limit, 0, 1, lcm, lambda_prime_power)
+}
+
+/// Builds `f(0..=limit)` for a function determined by its values on prime powers.
+///
+/// The linear sieve gives the smallest prime factor `p` of every `n`; splitting
+/// `n = p^e * rest` with `rest < n` lets each value be combined from one table
+/// lookup and one prime-power evaluation.
+fn multiplicative_table<T, C, F>(
+    limit: usize,
+    zero: T,
+    one: T,
+    combine: C,
+    prime_power: F,
+) -> Vec<T>
+where
+    T: Copy,
+    C: Fn(T, T) -> T,
+    F: Fn(u64, u32) -> T,
+{
+    let sieve = LinearSieve::new(limit);
+    let spf = &sieve.smallest_factor;
+
+    let mut values = vec![zero; limit + 1];
+    // rest[n] - n with every factor of its smallest prime removed, exponent[n] - how many
+    let mut rest = vec![0u32; limit + 1];
+    let mut exponent = vec![0u32; limit + 1];
+
+    if limit >= 1 {
+        values[1] = one;
+    }
+
+    for n in 2..=limit {
+        let p = spf[n];
+        let m = n / p as usize;
+
+        if spf[m] == p {
+            rest[n] = rest[m];
+            exponent[n] = exponent[m] + 1;
+        } else {
+            rest[n] = m as u32;
+            exponent[n] = 1;
+        }
+
+        values[n] = combine(values[rest[n] as usize], prime_power(p as u64, exponent[n]));
+    }
+
+    values
+}
+
+fn phi_prime_power(p: u64, e: u32) -> u64 {
+    p.pow(e - 1) * (p - 1)
+}
+
+fn mobius_prime_power(e: u32) -> i8 {
+    if e == 1 {
+        -1
+    } else {
+        0
+    }
+}
+
+/// 1 + p^k + p^2k + ... + p^ek
+fn sigma_prime_power(p: u64, e: u32, k: u32) -> u128 {
+    let term = (p as u128)
+        .checked_pow(k)
+        .expect("divisor sum overflows u128");
+
+    let mut sum: u128 = 1;
+    let mut power: u128 = 1;
+    for _ in 0..e {
+        power = power.checked_mul(term).expect("divisor sum overflows u128");
+        sum = sum.checked_add(power).expect("divisor sum overflows u128");
+    }
+    sum
+}
+
+fn lambda_prime_power(p: u64, e: u32) -> u64 {
+    if p == 2 && e >= 3 {
+        1 << (e - 2)
+    } else {
+        phi_prime_power(p, e)
+    }
+}
@@ -0,0 +1,289 @@
+use alloc::vec;
+
+use crate::bigint::BigUint;
+use crate::lucas::{self, LucasModulus};
+use crate::math_utils::is_prime_fast;
+use crate::montgomery::jacobi;
+use crate::random::RandomSource;
+use crate::small_primes::primes_below;
+
+/// Single candidates are trial-divided by the primes below this bound before the
+/// (much more expensive) probable-prime tests.
+const TRIAL_DIVISION_BOUND: u32 = 2_000;
+
+/// Prime searches sieve a whole window of candidates by the primes below this bound.
+const WINDOW_SIEVE_BOUND: u32 = 1 << 16;
+
+/// Number of consecutive integers sieved at once when searching for the next prime.
+const SEARCH_WINDOW: u64 = 1 << 12;
+
+/// Baillie-PSW test - strong base-2 Miller-Rabin followed by a strong Lucas test.
+///
+/// No counterexample is known; values that fit in `u64` are answered exactly.
+pub fn is_probable_prime(n: &BigUint) -> bool {
+    if let Some(n) = n.to_u64() {
+        return is_prime_fast(n);
+    }
+    baillie_psw(n)
+}
+
+/// [`is_probable_prime`] without the exact answer for values that fit in `u64` -
+/// the `BigUint` tests run on every input, which is what cross-checks need.
+pub fn baillie_psw(n: &BigUint) -> bool {
+    if n <= &BigUint::one() {
+        return false;
+    }
+    if let Some(p) = primes_below(TRIAL_DIVISION_BOUND).find(|&p| n.rem_u64(p as u64) == 0) {
+        return n == &BigUint::from(p as u64);
+    }
+
+    is_strong_probable_prime(n, &BigUint::from(2u64)) && is_strong_lucas_probable_prime(n)
+}
+
+/// Miller-Rabin with `rounds` random bases drawn from `rng`.
+///
+/// A composite passes with probability at most `4^-rounds`; values that fit in
+/// `u64` are answered exactly.
+pub fn miller_rabin<R: RandomSource>(n: &BigUint, rounds: u32, rng: &mut R) -> bool {
+    if let Some(n) = n.to_u64() {
+        return is_prime_fast(n);
+    }
+    miller_rabin_rounds(n, rounds, rng)
+}
+
+/// [`miller_rabin`] without the exact answer for values that fit in `u64`.
+pub fn miller_rabin_rounds<R: RandomSource>(n: &BigUint, rounds: u32, rng: &mut R) -> bool {
+    if let Some(n) = n.to_u64().filter(|&n| n < 5) {
+        return n == 2 || n == 3;
+    }
+    if n.is_even() {
+        return false;
+    }
+
+    // bases in 2..=n - 2
+    let bound = n - 3;
+    (0..rounds).all(|_| {
+        let base = BigUint::random_below(&bound, rng) + 2;
+        is_strong_probable_prime(n, &base)
+    })
+}
+
+/// Smallest probable prime `> n` according to [`is_probable_prime`].
+pub fn next_prime(n: &BigUint) -> BigUint {
+    if let Some(next) = n.to_u64().and_then(next_prime_u64) {
+        return BigUint::from(next);
+    }
+
+    let mut start = n + 1;
+    if start.is_even() {
+        start += 1;
+    }
+    loop {
+        if let Some(p) = search_window(&start) {
+            return p;
+        }
+        start += SEARCH_WINDOW;
+    }
+}
+
+/// Random probable prime with exactly `bits` bits (the top bit is always set).
+///
+/// A random odd starting point is followed up to the next prime, so primes after
+/// long gaps are slightly more likely than others - fine for experiments, not for
+/// keys that must follow a specific standard.
+///
+/// # Panics
+///
+/// Panics if `bits < 2`.
+pub fn random_prime<R: RandomSource>(bits: u64, rng: &mut R) -> BigUint {
+    assert!(bits >= 2, "a prime has at least 2 bits");
+
+    loop {
+        let mut start = BigUint::random(bits, rng);
+        start.set_bit(bits - 1);
+        start.set_bit(0);
+
+        let found = if bits <= 64 {
+            let n = start.to_u64().unwrap();
+            (n..=u64::MAX)
+                .step_by(2)
+                .find(|&n| is_prime_fast(n))
+                .map(BigUint::from)
+        } else {
+            search_window(&start)
+        };
+
+        match found {
+            Some(p) if p.bits() == bits => return p,
+            _ => continue,
+        }
+    }
+}
+
+/// Next prime for values small enough for [`is_prime_fast`], `None` if it does
+/// not fit in `u64`.
+fn next_prime_u64(n: u64) -> Option<u64> {
+    let start = n.checked_add(1)?;
+    (start..=u64::MAX).find(|&n| is_prime_fast(n))
+}
+
+/// First probable prime among the odd numbers in `start..start + SEARCH_WINDOW`,
+/// for an odd `start` above the sieving bound.
+///
+/// The small primes are sieved out of the whole window with one remainder each,
+/// instead of trial-dividing every candidate.
+fn search_window(start: &BigUint) -> Option<BigUint> {
+    // composite[i] - start + 2 i has a small factor
+    let mut composite = vec![false; (SEARCH_WINDOW / 2) as usize];
+    for p in primes_below(WINDOW_SIEVE_BOUND).skip(1) {
+        let p = p as u64;
+        // start + 2 i = 0 (mod p) for i = -start / 2, and (p + 1) / 2 is the inverse of 2
+        let mut i = (p - start.rem_u64(p)) % p * p.div_ceil(2) % p;
+        while i < composite.len() as u64 {
+            composite[i as usize] = true;
+            i += p;
+        }
+    }
+
+    let two = BigUint::from(2u64);
+    composite
+        .iter()
+        .enumerate()
+        .filter(|(_, &composite)| !composite)
+        .map(|(i, _)| start + 2 * i as u64)
+        .find(|candidate| {
+            is_strong_probable_prime(candidate, &two) && is_strong_lucas_probable_prime(candidate)
+        })
+}
+
+/// Jacobi symbol `(a / n)` for an odd `n`, through reciprocity so that only
+/// `n mod |a|` is ever computed.
+pub(crate) fn jacobi_big(a: i64, n: &BigUint) -> i32 {
+    let n_mod_8 = n.rem_u64(8);
+    let mut symbol = 1;
+    if a < 0 && n_mod_8 % 4 == 3 {
+        // (-1 / n)
+        symbol = -symbol;
+    }
+
+    let mut a = a.unsigned_abs();
+    if a == 0 {
+        return (n == &BigUint::one()) as i32;
+    }
+    let twos = a.trailing_zeros();
+    a >>= twos;
+    if twos % 2 == 1 && (n_mod_8 == 3 || n_mod_8 == 5) {
+        // (2 / n)
+        symbol = -symbol;
+    }
+
+    // (a / n) = (n mod a / a), up to the sign (-1)^((a - 1) / 2 * (n - 1) / 2)
+    if a % 4 == 3 && n_mod_8 % 4 == 3 {
+        symbol = -symbol;
+    }
+    symbol * jacobi(n.rem_u64(a) as i128, a as u128)
+}
+
+fn is_strong_probable_prime(n: &BigUint, base: &BigUint) -> bool {
+    let n_minus_one = n - 1;
+    let s = n_minus_one.trailing_zeros().unwrap();
+    let d = &n_minus_one >> s;
+
+    let mut x = base.pow_mod(&d, n);
+    if x == BigUint::one() || x == n_minus_one {
+        return true;
+    }
+    for _ in 1..s {
+        x = x.square() % n;
+        if x == n_minus_one {
+            return true;
+        }
+        if x == BigUint::one() {
+            return false;
+        }
+    }
+    false
+}
+
+/// An odd modulus without factors below [`TRIAL_DIVISION_BOUND`], for the strong
+/// Lucas test.
+pub(crate) struct BigModulus<'a> {
+    n: &'a BigUint,
+    n_plus_one: BigUint,
+}
+
+impl<'a> BigModulus<'a> {
+    pub(crate) fn new(n: &'a BigUint) -> BigModulus<'a> {
+        BigModulus {
+            n,
+            n_plus_one: n + 1,
+        }
+    }
+}
+
+impl LucasModulus for BigModulus<'_> {
+    type Residue = BigUint;
+
+    fn residue(&self, v: i64) -> BigUint {
+        if v < 0 {
+            self.n - v.unsigned_abs()
+        } else {
+            BigUint::from(v as u64)
+        }
+    }
+
+    fn add(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        let sum = a + b;
+        if &sum >= self.n {
+            sum - self.n
+        } else {
+            sum
+        }
+    }
+
+    fn sub(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        if a >= b {
+            a - b
+        } else {
+            a + self.n - b
+        }
+    }
+
+    fn mul(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        a * b % self.n
+    }
+
+    fn half(&self, a: &BigUint) -> BigUint {
+        if a.is_even() {
+            a >> 1
+        } else {
+            (a + self.n) >> 1
+        }
+    }
+
+    fn is_zero(&self, a: &BigUint) -> bool {
+        a.is_zero()
+    }
+
+    fn jacobi(&self, a: i64) -> i32 {
+        jacobi_big(a, self.n)
+    }
+
+    fn is_square(&self) -> bool {
+        let root = self.n.isqrt();
+        &(&root * &root) == self.n
+    }
+
+    fn n_plus_one_bits(&self) -> u64 {
+        self.n_plus_one.bits()
+    }
+
+    fn n_plus_one_bit(&self, i: u64) -> bool {
+        self.n_plus_one.bit(i)
+    }
+}
+
+/// Strong Lucas test for odd `n` without factors below [`TRIAL_DIVISION_BOUND`].
+fn is_strong_lucas_probable_prime(n: &BigUint) -> bool {
+    lucas::is_strong_lucas_probable_prime(&BigModulus::new(n))
+}
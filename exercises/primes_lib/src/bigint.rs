@@ -0,0 +1,930 @@
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Add, AddAssign, Div, Mul, MulAssign, Rem, Shl, Shr, Sub, SubAssign};
+use core::str::FromStr;
+
+use crate::montgomery::inverse_mod_2_128;
+use crate::random::RandomSource;
+
+/// Operands with at least this many limbs are multiplied with Karatsuba's method,
+/// shorter ones with the schoolbook algorithm.
+const KARATSUBA_THRESHOLD: usize = 32;
+
+/// Arbitrary-precision unsigned integer.
+///
+/// Stored as little-endian `u64` limbs without trailing zero limbs, so zero has no
+/// limbs and every value has exactly one representation. Arithmetic follows the
+/// primitive types: subtraction below zero and division by zero panic.
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigUint {
+    limbs: Vec<u64>,
+}
+
+impl BigUint {
+    pub fn zero() -> BigUint {
+        BigUint { limbs: Vec::new() }
+    }
+
+    pub fn one() -> BigUint {
+        BigUint { limbs: vec![1] }
+    }
+
+    fn from_limbs(mut limbs: Vec<u64>) -> BigUint {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        BigUint { limbs }
+    }
+
+    /// Parses big-endian bytes, leading zeros are allowed.
+    pub fn from_bytes_be(bytes: &[u8]) -> BigUint {
+        let limbs = bytes
+            .rchunks(8)
+            .map(|chunk| chunk.iter().fold(0, |acc, &byte| acc << 8 | byte as u64))
+            .collect();
+        BigUint::from_limbs(limbs)
+    }
+
+    /// Minimal big-endian bytes - empty for zero.
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = self
+            .limbs
+            .iter()
+            .rev()
+            .flat_map(|limb| limb.to_be_bytes())
+            .collect();
+        let leading_zeros = bytes.iter().take_while(|&&byte| byte == 0).count();
+        bytes.drain(..leading_zeros);
+        bytes
+    }
+
+    /// Parses digits in `radix`; `_` separators are skipped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not in `2..=36`.
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<BigUint, ParseBigUintError> {
+        assert!((2..=36).contains(&radix), "radix must be in 2..=36");
+
+        let mut digits = s.chars().filter(|&c| c != '_').peekable();
+        if digits.peek().is_none() {
+            return Err(ParseBigUintError {
+                message: "cannot parse integer from empty string",
+            });
+        }
+
+        let mut limbs = Vec::new();
+        for c in digits {
+            let digit = c.to_digit(radix).ok_or(ParseBigUintError {
+                message: "invalid digit found in string",
+            })?;
+            mul_add_small(&mut limbs, radix as u64, digit as u64);
+        }
+        Ok(BigUint::from_limbs(limbs))
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn is_odd(&self) -> bool {
+        self.limbs.first().is_some_and(|&limb| limb & 1 == 1)
+    }
+
+    pub fn is_even(&self) -> bool {
+        !self.is_odd()
+    }
+
+    /// Number of significant bits, `0` for zero.
+    pub fn bits(&self) -> u64 {
+        match self.limbs.last() {
+            Some(&top) => self.limbs.len() as u64 * 64 - top.leading_zeros() as u64,
+            None => 0,
+        }
+    }
+
+    pub fn bit(&self, i: u64) -> bool {
+        self.limbs
+            .get((i / 64) as usize)
+            .is_some_and(|&limb| limb >> (i % 64) & 1 == 1)
+    }
+
+    pub fn set_bit(&mut self, i: u64) {
+        let index = (i / 64) as usize;
+        if index >= self.limbs.len() {
+            self.limbs.resize(index + 1, 0);
+        }
+        self.limbs[index] |= 1 << (i % 64);
+    }
+
+    /// Index of the lowest set bit, `None` for zero.
+    pub fn trailing_zeros(&self) -> Option<u64> {
+        let index = self.limbs.iter().position(|&limb| limb != 0)?;
+        Some(index as u64 * 64 + self.limbs[index].trailing_zeros() as u64)
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs[..] {
+            [] => Some(0),
+            [low] => Some(low),
+            _ => None,
+        }
+    }
+
+    pub fn to_u128(&self) -> Option<u128> {
+        match self.limbs[..] {
+            [] => Some(0),
+            [low] => Some(low as u128),
+            [low, high] => Some((high as u128) << 64 | low as u128),
+            _ => None,
+        }
+    }
+
+    /// Uniformly random value below `2^bits`.
+    pub fn random<R: RandomSource>(bits: u64, rng: &mut R) -> BigUint {
+        let count = bits.div_ceil(64) as usize;
+        let mut limbs: Vec<u64> = (0..count).map(|_| rng.next_u64()).collect();
+        if !bits.is_multiple_of(64) {
+            limbs[count - 1] >>= 64 - bits % 64;
+        }
+        BigUint::from_limbs(limbs)
+    }
+
+    /// Uniformly random value below `bound` (rejection sampling).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bound` is zero.
+    pub fn random_below<R: RandomSource>(bound: &BigUint, rng: &mut R) -> BigUint {
+        assert!(!bound.is_zero(), "random_below needs a positive bound");
+
+        let bits = bound.bits();
+        loop {
+            let candidate = BigUint::random(bits, rng);
+            if &candidate < bound {
+                return candidate;
+            }
+        }
+    }
+
+    pub fn checked_sub(&self, rhs: &BigUint) -> Option<BigUint> {
+        if self < rhs {
+            return None;
+        }
+
+        let mut limbs = self.limbs.clone();
+        let mut borrow = false;
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let other = rhs.limbs.get(i).copied().unwrap_or(0);
+            if other == 0 && !borrow && i >= rhs.limbs.len() {
+                break;
+            }
+            let (difference, overflow1) = limb.overflowing_sub(other);
+            let (difference, overflow2) = difference.overflowing_sub(borrow as u64);
+            *limb = difference;
+            borrow = overflow1 || overflow2;
+        }
+        Some(BigUint::from_limbs(limbs))
+    }
+
+    /// Quotient and remainder in one division.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisor` is zero.
+    pub fn div_rem(&self, divisor: &BigUint) -> (BigUint, BigUint) {
+        assert!(!divisor.is_zero(), "attempt to divide by zero");
+
+        if self < divisor {
+            return (BigUint::zero(), self.clone());
+        }
+        if let [d] = divisor.limbs[..] {
+            let (quotient, remainder) = div_rem_small(&self.limbs, d);
+            return (BigUint::from_limbs(quotient), BigUint::from(remainder));
+        }
+
+        let (quotient, remainder) = div_rem_knuth(&self.limbs, &divisor.limbs);
+        (
+            BigUint::from_limbs(quotient),
+            BigUint::from_limbs(remainder),
+        )
+    }
+
+    /// `self % d` for a machine-word divisor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `d` is zero.
+    pub fn rem_u64(&self, d: u64) -> u64 {
+        assert!(
+            d != 0,
+            "attempt to calculate the remainder with a divisor of zero"
+        );
+        self.limbs.iter().rev().fold(0, |rem, &limb| {
+            (((rem as u128) << 64 | limb as u128) % d as u128) as u64
+        })
+    }
+
+    /// `self * self` - about half the limb products of a general multiplication.
+    pub fn square(&self) -> BigUint {
+        BigUint::from_limbs(square_limbs(&self.limbs))
+    }
+
+    /// `self mod 2^bits`.
+    pub fn low_bits(&self, bits: u64) -> BigUint {
+        let index = (bits / 64) as usize;
+        if index >= self.limbs.len() {
+            return self.clone();
+        }
+        let mut limbs = self.limbs[..=index].to_vec();
+        limbs[index] &= (1 << (bits % 64)) - 1;
+        BigUint::from_limbs(limbs)
+    }
+
+    pub fn pow(&self, mut exp: u32) -> BigUint {
+        let mut base = self.clone();
+        let mut result = BigUint::one();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = &result * &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = base.square();
+            }
+        }
+        result
+    }
+
+    /// `self^exp mod m` - odd moduli use Montgomery multiplication with a 4-bit window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `m` is zero.
+    pub fn pow_mod(&self, exp: &BigUint, m: &BigUint) -> BigUint {
+        assert!(!m.is_zero(), "pow_mod needs a nonzero modulus");
+
+        if m == &BigUint::one() {
+            return BigUint::zero();
+        }
+        if m.is_even() {
+            return pow_mod_plain(self, exp, m);
+        }
+
+        let mont = MontgomeryBig::new(m);
+        let base = mont.to_mont(self);
+
+        // table[i] = base^i in Montgomery form
+        let mut table = vec![mont.one.clone()];
+        for i in 1..16 {
+            table.push(mont.mul(&table[i - 1], &base));
+        }
+
+        let mut result = mont.one.clone();
+        let windows = exp.bits().div_ceil(4);
+        for window in (0..windows).rev() {
+            if window + 1 != windows {
+                for _ in 0..4 {
+                    result = mont.mul(&result, &result);
+                }
+            }
+            let nibble = (0..4).fold(0, |acc, i| acc | (exp.bit(window * 4 + i) as usize) << i);
+            if nibble != 0 {
+                result = mont.mul(&result, &table[nibble]);
+            }
+        }
+
+        mont.to_plain(&result)
+    }
+
+    /// Largest `r` with `r * r <= self` (Newton's method).
+    pub fn isqrt(&self) -> BigUint {
+        if self.is_zero() {
+            return BigUint::zero();
+        }
+
+        // start above the root, every step moves down until the sequence stops decreasing
+        let mut x = BigUint::one() << self.bits().div_ceil(2);
+        loop {
+            let y = (&x + self / &x) >> 1;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+}
+
+/// Error returned when a [`BigUint`] cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBigUintError {
+    message: &'static str,
+}
+
+impl fmt::Display for ParseBigUintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message)
+    }
+}
+
+impl core::error::Error for ParseBigUintError {}
+
+impl FromStr for BigUint {
+    type Err = ParseBigUintError;
+
+    fn from_str(s: &str) -> Result<BigUint, ParseBigUintError> {
+        BigUint::from_str_radix(s, 10)
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+
+        let mut chunks = Vec::new();
+        let mut rest = self.limbs.clone();
+        while !rest.is_empty() {
+            let (quotient, remainder) = div_rem_small(&rest, CHUNK);
+            chunks.push(remainder);
+            rest = BigUint::from_limbs(quotient).limbs;
+        }
+
+        let mut digits = chunks.pop().unwrap_or(0).to_string();
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{:019}", chunk));
+        }
+        f.pad_integral(true, "", &digits)
+    }
+}
+
+impl fmt::Debug for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::LowerHex for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut digits = match self.limbs.last() {
+            Some(top) => format!("{:x}", top),
+            None => "0".to_string(),
+        };
+        for limb in self.limbs.iter().rev().skip(1) {
+            digits.push_str(&format!("{:016x}", limb));
+        }
+        f.pad_integral(true, "0x", &digits)
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &BigUint) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &BigUint) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+macro_rules! impl_from_primitive {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for BigUint {
+                fn from(value: $t) -> BigUint {
+                    let value = value as u128;
+                    BigUint::from_limbs(vec![value as u64, (value >> 64) as u64])
+                }
+            }
+        )*
+    };
+}
+
+impl_from_primitive!(u8, u16, u32, u64, u128, usize);
+
+fn add(a: &BigUint, b: &BigUint) -> BigUint {
+    let (long, short) = if a.limbs.len() >= b.limbs.len() {
+        (a, b)
+    } else {
+        (b, a)
+    };
+
+    let mut limbs = Vec::with_capacity(long.limbs.len() + 1);
+    let mut carry = false;
+    for (i, &limb) in long.limbs.iter().enumerate() {
+        let other = short.limbs.get(i).copied().unwrap_or(0);
+        let (sum, overflow1) = limb.overflowing_add(other);
+        let (sum, overflow2) = sum.overflowing_add(carry as u64);
+        limbs.push(sum);
+        carry = overflow1 || overflow2;
+    }
+    limbs.push(carry as u64);
+
+    BigUint::from_limbs(limbs)
+}
+
+fn sub(a: &BigUint, b: &BigUint) -> BigUint {
+    a.checked_sub(b).expect("attempt to subtract with overflow")
+}
+
+fn mul(a: &BigUint, b: &BigUint) -> BigUint {
+    BigUint::from_limbs(mul_limbs(&a.limbs, &b.limbs))
+}
+
+/// Full product, `a.len() + b.len()` limbs.
+fn mul_limbs(a: &[u64], b: &[u64]) -> Vec<u64> {
+    if a.len().min(b.len()) < KARATSUBA_THRESHOLD {
+        return mul_schoolbook(a, b);
+    }
+
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    if 2 * short.len() <= long.len() {
+        // too unbalanced to split both - multiply by one slice of `long` at a time
+        let mut result = vec![0u64; a.len() + b.len()];
+        for (i, chunk) in long.chunks(short.len()).enumerate() {
+            add_assign_limbs(&mut result[i * short.len()..], &mul_limbs(short, chunk));
+        }
+        return result;
+    }
+
+    // a = a1 * B + a0, b = b1 * B + b0 with B = 2^(64 half):
+    // a * b = z2 * B^2 + (z1 - z2 - z0) * B + z0 with z1 = (a0 + a1)(b0 + b1)
+    let half = long.len() / 2;
+    let (a0, a1) = a.split_at(half);
+    let (b0, b1) = b.split_at(half);
+
+    let z0 = mul_limbs(a0, b0);
+    let z2 = mul_limbs(a1, b1);
+    let mut z1 = mul_limbs(&add_limbs(a0, a1), &add_limbs(b0, b1));
+    sub_assign_limbs(&mut z1, &z0);
+    sub_assign_limbs(&mut z1, &z2);
+
+    combine_karatsuba(a.len() + b.len(), half, &z0, &z1, &z2)
+}
+
+/// Square, `2 * a.len()` limbs.
+fn square_limbs(a: &[u64]) -> Vec<u64> {
+    if a.len() < KARATSUBA_THRESHOLD {
+        return square_schoolbook(a);
+    }
+
+    let half = a.len() / 2;
+    let (a0, a1) = a.split_at(half);
+    let z0 = square_limbs(a0);
+    let z2 = square_limbs(a1);
+    let mut z1 = square_limbs(&add_limbs(a0, a1));
+    sub_assign_limbs(&mut z1, &z0);
+    sub_assign_limbs(&mut z1, &z2);
+
+    combine_karatsuba(2 * a.len(), half, &z0, &z1, &z2)
+}
+
+fn combine_karatsuba(len: usize, half: usize, z0: &[u64], z1: &[u64], z2: &[u64]) -> Vec<u64> {
+    let mut result = vec![0u64; len];
+    add_assign_limbs(&mut result, z0);
+    add_assign_limbs(&mut result[half..], z1);
+    add_assign_limbs(&mut result[2 * half..], z2);
+    result
+}
+
+fn mul_schoolbook(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut limbs = vec![0u64; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u128;
+        for (j, &y) in b.iter().enumerate() {
+            let product = x as u128 * y as u128 + limbs[i + j] as u128 + carry;
+            limbs[i + j] = product as u64;
+            carry = product >> 64;
+        }
+        limbs[i + b.len()] = carry as u64;
+    }
+    limbs
+}
+
+fn square_schoolbook(a: &[u64]) -> Vec<u64> {
+    // the products a[i] * a[j] with i < j appear twice - sum them once, double, add the diagonal
+    let mut limbs = vec![0u64; 2 * a.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u128;
+        for (j, &y) in a.iter().enumerate().skip(i + 1) {
+            let product = x as u128 * y as u128 + limbs[i + j] as u128 + carry;
+            limbs[i + j] = product as u64;
+            carry = product >> 64;
+        }
+        limbs[i + a.len()] = carry as u64;
+    }
+
+    let mut carry = 0u128;
+    for (i, &x) in a.iter().enumerate() {
+        let square = x as u128 * x as u128;
+        for (k, part) in [(2 * i, square as u64), (2 * i + 1, (square >> 64) as u64)] {
+            let value = ((limbs[k] as u128) << 1) + part as u128 + carry;
+            limbs[k] = value as u64;
+            carry = value >> 64;
+        }
+    }
+    limbs
+}
+
+/// `a + b`, one limb longer than the longer operand.
+fn add_limbs(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut sum = a.to_vec();
+    sum.resize(a.len().max(b.len()) + 1, 0);
+    add_assign_limbs(&mut sum, b);
+    sum
+}
+
+/// `a += b`, the carry out of `a` is dropped.
+fn add_assign_limbs(a: &mut [u64], b: &[u64]) {
+    let mut carry = false;
+    for (i, limb) in a.iter_mut().enumerate() {
+        let other = b.get(i).copied().unwrap_or(0);
+        if i >= b.len() && !carry {
+            break;
+        }
+        let (sum, overflow1) = limb.overflowing_add(other);
+        let (sum, overflow2) = sum.overflowing_add(carry as u64);
+        *limb = sum;
+        carry = overflow1 || overflow2;
+    }
+}
+
+/// `a -= b` for `a >= b`.
+fn sub_assign_limbs(a: &mut [u64], b: &[u64]) {
+    let mut borrow = false;
+    for (i, limb) in a.iter_mut().enumerate() {
+        let other = b.get(i).copied().unwrap_or(0);
+        if i >= b.len() && !borrow {
+            break;
+        }
+        let (difference, underflow1) = limb.overflowing_sub(other);
+        let (difference, underflow2) = difference.overflowing_sub(borrow as u64);
+        *limb = difference;
+        borrow = underflow1 || underflow2;
+    }
+}
+
+fn div(a: &BigUint, b: &BigUint) -> BigUint {
+    a.div_rem(b).0
+}
+
+fn rem(a: &BigUint, b: &BigUint) -> BigUint {
+    a.div_rem(b).1
+}
+
+/// `limbs = limbs * factor + addend` in place.
+fn mul_add_small(limbs: &mut Vec<u64>, factor: u64, addend: u64) {
+    let mut carry = addend as u128;
+    for limb in limbs.iter_mut() {
+        let value = *limb as u128 * factor as u128 + carry;
+        *limb = value as u64;
+        carry = value >> 64;
+    }
+    if carry > 0 {
+        limbs.push(carry as u64);
+    }
+}
+
+fn div_rem_small(limbs: &[u64], d: u64) -> (Vec<u64>, u64) {
+    let mut quotient = vec![0u64; limbs.len()];
+    let mut remainder = 0u128;
+    for i in (0..limbs.len()).rev() {
+        let value = remainder << 64 | limbs[i] as u128;
+        quotient[i] = (value / d as u128) as u64;
+        remainder = value % d as u128;
+    }
+    (quotient, remainder as u64)
+}
+
+/// Knuth's algorithm D (TAOCP 4.3.1) for a divisor of at least two limbs.
+fn div_rem_knuth(a: &[u64], b: &[u64]) -> (Vec<u64>, Vec<u64>) {
+    const BASE: u128 = 1 << 64;
+
+    // normalize so the top limb of the divisor has its high bit set
+    let shift = b.last().unwrap().leading_zeros();
+    let v = shl_limbs(b, shift);
+    let mut u = shl_limbs(a, shift);
+    u.resize(a.len() + 1, 0);
+
+    let n = v.len();
+    let m = u.len() - n - 1;
+    let mut quotient = vec![0u64; m + 1];
+
+    for j in (0..=m).rev() {
+        let numerator = (u[j + n] as u128) << 64 | u[j + n - 1] as u128;
+        let mut q_hat = numerator / v[n - 1] as u128;
+        let mut r_hat = numerator % v[n - 1] as u128;
+        while q_hat >= BASE || q_hat * v[n - 2] as u128 > (r_hat << 64 | u[j + n - 2] as u128) {
+            q_hat -= 1;
+            r_hat += v[n - 1] as u128;
+            if r_hat >= BASE {
+                break;
+            }
+        }
+
+        // u[j..=j + n] -= q_hat * v
+        let mut carry = 0u128;
+        let mut borrow = 0u64;
+        for i in 0..n {
+            let product = q_hat * v[i] as u128 + carry;
+            carry = product >> 64;
+            let (difference, overflow1) = u[i + j].overflowing_sub(product as u64);
+            let (difference, overflow2) = difference.overflowing_sub(borrow);
+            u[i + j] = difference;
+            borrow = overflow1 as u64 + overflow2 as u64;
+        }
+        let (difference, overflow1) = u[j + n].overflowing_sub(carry as u64);
+        let (difference, overflow2) = difference.overflowing_sub(borrow);
+        u[j + n] = difference;
+
+        if overflow1 || overflow2 {
+            // q_hat was one too large - add the divisor back
+            q_hat -= 1;
+            let mut carry = false;
+            for i in 0..n {
+                let (sum, overflow1) = u[i + j].overflowing_add(v[i]);
+                let (sum, overflow2) = sum.overflowing_add(carry as u64);
+                u[i + j] = sum;
+                carry = overflow1 || overflow2;
+            }
+            u[j + n] = u[j + n].wrapping_add(carry as u64);
+        }
+
+        quotient[j] = q_hat as u64;
+    }
+
+    u.truncate(n);
+    (quotient, shr_limbs(&u, shift))
+}
+
+/// Shifts left by less than one limb, growing by one limb if bits are carried out.
+fn shl_limbs(limbs: &[u64], shift: u32) -> Vec<u64> {
+    if shift == 0 {
+        return limbs.to_vec();
+    }
+
+    let mut result = Vec::with_capacity(limbs.len() + 1);
+    let mut carry = 0;
+    for &limb in limbs {
+        result.push(limb << shift | carry);
+        carry = limb >> (64 - shift);
+    }
+    if carry != 0 {
+        result.push(carry);
+    }
+    result
+}
+
+/// Shifts right by less than one limb.
+fn shr_limbs(limbs: &[u64], shift: u32) -> Vec<u64> {
+    if shift == 0 {
+        return limbs.to_vec();
+    }
+
+    (0..limbs.len())
+        .map(|i| {
+            let high = limbs.get(i + 1).map_or(0, |&next| next << (64 - shift));
+            limbs[i] >> shift | high
+        })
+        .collect()
+}
+
+fn shl(a: &BigUint, bits: u64) -> BigUint {
+    if a.is_zero() {
+        return BigUint::zero();
+    }
+
+    let mut limbs = vec![0u64; (bits / 64) as usize];
+    limbs.extend(shl_limbs(&a.limbs, (bits % 64) as u32));
+    BigUint::from_limbs(limbs)
+}
+
+fn shr(a: &BigUint, bits: u64) -> BigUint {
+    let skip = (bits / 64) as usize;
+    if skip >= a.limbs.len() {
+        return BigUint::zero();
+    }
+    BigUint::from_limbs(shr_limbs(&a.limbs[skip..], (bits % 64) as u32))
+}
+
+fn pow_mod_plain(base: &BigUint, exp: &BigUint, m: &BigUint) -> BigUint {
+    let mut result = BigUint::one();
+    let base = base % m;
+    for i in (0..exp.bits()).rev() {
+        result = &result * &result % m;
+        if exp.bit(i) {
+            result = &result * &base % m;
+        }
+    }
+    result
+}
+
+/// Montgomery multiplication modulo an odd `BigUint` with R = 2^(64 k) for a
+/// `k`-limb modulus. Residues are kept as exactly `k` limbs.
+struct MontgomeryBig {
+    n: Vec<u64>,
+    /// -n^(-1) mod 2^64
+    n_neg_inv: u64,
+    /// R^2 mod n
+    r2: Vec<u64>,
+    /// R mod n - the Montgomery form of 1
+    one: Vec<u64>,
+}
+
+impl MontgomeryBig {
+    fn new(n: &BigUint) -> MontgomeryBig {
+        let k = n.limbs.len();
+
+        let inv = inverse_mod_2_128(n.limbs[0] as u128) as u64;
+
+        let r = BigUint::one() << (64 * k as u64);
+        let one = &r % n;
+        let r2 = &one * &one % n;
+
+        MontgomeryBig {
+            n: n.limbs.clone(),
+            n_neg_inv: inv.wrapping_neg(),
+            r2: MontgomeryBig::pad(&r2, k),
+            one: MontgomeryBig::pad(&one, k),
+        }
+    }
+
+    fn pad(a: &BigUint, k: usize) -> Vec<u64> {
+        let mut limbs = a.limbs.clone();
+        limbs.resize(k, 0);
+        limbs
+    }
+
+    fn to_mont(&self, a: &BigUint) -> Vec<u64> {
+        let modulus = BigUint::from_limbs(self.n.clone());
+        let reduced = MontgomeryBig::pad(&(a % &modulus), self.n.len());
+        self.mul(&reduced, &self.r2)
+    }
+
+    fn to_plain(&self, a: &[u64]) -> BigUint {
+        let mut one = vec![0u64; self.n.len()];
+        one[0] = 1;
+        BigUint::from_limbs(self.mul(a, &one))
+    }
+
+    /// a * b * R^(-1) mod n (CIOS - multiplication and reduction interleaved).
+    fn mul(&self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        let k = self.n.len();
+        let mut t = vec![0u64; k + 2];
+
+        for &b_i in &b[..k] {
+            let mut carry = 0u128;
+            for (t_j, &a_j) in t[..k].iter_mut().zip(&a[..k]) {
+                let value = *t_j as u128 + a_j as u128 * b_i as u128 + carry;
+                *t_j = value as u64;
+                carry = value >> 64;
+            }
+            let value = t[k] as u128 + carry;
+            t[k] = value as u64;
+            t[k + 1] = (value >> 64) as u64;
+
+            // adding m * n clears the low limb, which is then shifted out
+            let m = t[0].wrapping_mul(self.n_neg_inv);
+            let mut carry = 0u128;
+            for (t_j, &n_j) in t[..k].iter_mut().zip(&self.n) {
+                let value = *t_j as u128 + m as u128 * n_j as u128 + carry;
+                *t_j = value as u64;
+                carry = value >> 64;
+            }
+            let value = t[k] as u128 + carry;
+            t[k] = value as u64;
+            t[k + 1] += (value >> 64) as u64;
+            t.copy_within(1.., 0);
+            t[k + 1] = 0;
+        }
+
+        // t < 2n - one conditional subtraction
+        let needs_subtraction = t[k] != 0 || t[..k].iter().rev().cmp(self.n.iter().rev()).is_ge();
+        t.truncate(k);
+        if needs_subtraction {
+            let mut borrow = false;
+            for (limb, &n_j) in t.iter_mut().zip(&self.n) {
+                let (difference, overflow1) = limb.overflowing_sub(n_j);
+                let (difference, overflow2) = difference.overflowing_sub(borrow as u64);
+                *limb = difference;
+                borrow = overflow1 || overflow2;
+            }
+        }
+        t
+    }
+}
+
+macro_rules! impl_binary_op {
+    ($trait:ident, $method:ident, $function:ident) => {
+        impl $trait<&BigUint> for &BigUint {
+            type Output = BigUint;
+
+            fn $method(self, rhs: &BigUint) -> BigUint {
+                $function(self, rhs)
+            }
+        }
+
+        impl $trait<BigUint> for &BigUint {
+            type Output = BigUint;
+
+            fn $method(self, rhs: BigUint) -> BigUint {
+                $function(self, &rhs)
+            }
+        }
+
+        impl $trait<&BigUint> for BigUint {
+            type Output = BigUint;
+
+            fn $method(self, rhs: &BigUint) -> BigUint {
+                $function(&self, rhs)
+            }
+        }
+
+        impl $trait<BigUint> for BigUint {
+            type Output = BigUint;
+
+            fn $method(self, rhs: BigUint) -> BigUint {
+                $function(&self, &rhs)
+            }
+        }
+
+        impl $trait<u64> for &BigUint {
+            type Output = BigUint;
+
+            fn $method(self, rhs: u64) -> BigUint {
+                $function(self, &BigUint::from(rhs))
+            }
+        }
+
+        impl $trait<u64> for BigUint {
+            type Output = BigUint;
+
+            fn $method(self, rhs: u64) -> BigUint {
+                $function(&self, &BigUint::from(rhs))
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, add);
+impl_binary_op!(Sub, sub, sub);
+impl_binary_op!(Mul, mul, mul);
+impl_binary_op!(Div, div, div);
+impl_binary_op!(Rem, rem, rem);
+
+macro_rules! impl_assign_op {
+    ($trait:ident, $method:ident, $function:ident) => {
+        impl $trait<&BigUint> for BigUint {
+            fn $method(&mut self, rhs: &BigUint) {
+                *self = $function(self, rhs);
+            }
+        }
+
+        impl $trait<BigUint> for BigUint {
+            fn $method(&mut self, rhs: BigUint) {
+                *self = $function(self, &rhs);
+            }
+        }
+
+        impl $trait<u64> for BigUint {
+            fn $method(&mut self, rhs: u64) {
+                *self = $function(self, &BigUint::from(rhs));
+            }
+        }
+    };
+}
+
+impl_assign_op!(AddAssign, add_assign, add);
+impl_assign_op!(SubAssign, sub_assign, sub);
+impl_assign_op!(MulAssign, mul_assign, mul);
+
+macro_rules! impl_shift_op {
+    ($trait:ident, $method:ident, $function:ident) => {
+        impl $trait<u64> for &BigUint {
+            type Output = BigUint;
+
+            fn $method(self, bits: u64) -> BigUint {
+                $function(self, bits)
+            }
+        }
+
+        impl $trait<u64> for BigUint {
+            type Output = BigUint;
+
+            fn $method(self, bits: u64) -> BigUint {
+                $function(&self, bits)
+            }
+        }
+    };
+}
+
+impl_shift_op!(Shl, shl, shl);
+impl_shift_op!(Shr, shr, shr);
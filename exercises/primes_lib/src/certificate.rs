@@ -0,0 +1,204 @@
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use crate::factor::factorize;
+use crate::math_utils::is_prime;
+use crate::modular::mod_pow;
+
+/// One step of a Pratt certificate: `witness` has order exactly `prime - 1` modulo
+/// `prime`, and `factors` are the distinct prime factors of `prime - 1`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrattStep {
+    pub prime: u64,
+    pub witness: u64,
+    pub factors: Vec<u64>,
+}
+
+/// Pratt certificate of primality.
+///
+/// The steps are ordered so that every factor is certified by an earlier step;
+/// `2` is the only step accepted without a witness. The last step certifies `prime`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Certificate {
+    pub prime: u64,
+    pub steps: Vec<PrattStep>,
+}
+
+/// Builds a Pratt certificate for `n`, `None` if `n` is not prime.
+pub fn certify(n: u64) -> Option<Certificate> {
+    if !is_prime(n) {
+        return None;
+    }
+
+    let mut steps = Vec::new();
+    let mut certified = BTreeSet::new();
+    push_steps(n, &mut steps, &mut certified);
+
+    Some(Certificate { prime: n, steps })
+}
+
+/// Appends the steps for `p` after the steps for the factors of `p - 1`.
+fn push_steps(p: u64, steps: &mut Vec<PrattStep>, certified: &mut BTreeSet<u64>) {
+    if !certified.insert(p) {
+        return;
+    }
+    if p == 2 {
+        steps.push(PrattStep {
+            prime: 2,
+            witness: 1,
+            factors: Vec::new(),
+        });
+        return;
+    }
+
+    let factors: Vec<u64> = factorize(p - 1).into_iter().map(|(q, _)| q).collect();
+    for &q in &factors {
+        push_steps(q, steps, certified);
+    }
+
+    // p is prime, so a primitive root exists and the smallest one is small
+    let witness = (2..p)
+        .find(|&a| factors.iter().all(|&q| mod_pow(a, (p - 1) / q, p) != 1))
+        .unwrap();
+
+    steps.push(PrattStep {
+        prime: p,
+        witness,
+        factors,
+    });
+}
+
+/// Checks a certificate using nothing but modular exponentiation - neither the
+/// primality tests nor the factorization of this crate are trusted.
+pub fn verify_certificate(certificate: &Certificate) -> bool {
+    let mut verified = BTreeSet::new();
+
+    for step in &certificate.steps {
+        if !verify_step(step, &verified) {
+            return false;
+        }
+        verified.insert(step.prime);
+    }
+
+    certificate
+        .steps
+        .last()
+        .is_some_and(|step| step.prime == certificate.prime)
+}
+
+fn verify_step(step: &PrattStep, verified: &BTreeSet<u64>) -> bool {
+    let p = step.prime;
+    if p == 2 {
+        return step.factors.is_empty();
+    }
+    if p < 3 || step.witness == 0 || step.witness >= p {
+        return false;
+    }
+
+    // the listed primes must account for all of p - 1
+    let mut rest = p - 1;
+    for (i, &q) in step.factors.iter().enumerate() {
+        if !verified.contains(&q) || step.factors[..i].contains(&q) {
+            return false;
+        }
+        while rest.is_multiple_of(q) {
+            rest /= q;
+        }
+    }
+    if rest != 1 {
+        return false;
+    }
+
+    // the witness has order p - 1, so the multiplicative group has p - 1 elements
+    mod_pow(step.witness, p - 1, p) == 1
+        && step
+            .factors
+            .iter()
+            .all(|&q| mod_pow(step.witness, (p - 1) / q, p) != 1)
+}
+
+/// Text form: a `pratt <prime>` header followed by one `prime witness factors...`
+/// line per step.
+///
+/// ```text
+/// pratt 23
+/// 2 1
+/// 5 2 2
+/// 11 2 2 5
+/// 23 5 2 11
+/// ```
+impl fmt::Display for Certificate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "pratt {}", self.prime)?;
+        for step in &self.steps {
+            write!(f, "{} {}", step.prime, step.witness)?;
+            for q in &step.factors {
+                write!(f, " {}", q)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Error returned when the text form of a [`Certificate`] cannot be parsed.
+///
+/// Parsing only checks the syntax - use [`verify_certificate`] on the result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseCertificateError {
+    line: usize,
+    message: &'static str,
+}
+
+impl fmt::Display for ParseCertificateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl core::error::Error for ParseCertificateError {}
+
+/// Blank lines and lines starting with `#` are ignored.
+impl FromStr for Certificate {
+    type Err = ParseCertificateError;
+
+    fn from_str(s: &str) -> Result<Certificate, ParseCertificateError> {
+        let mut lines = s
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+        let error = |line, message| ParseCertificateError { line, message };
+
+        let (header_line, header) = lines.next().ok_or(error(0, "empty certificate"))?;
+        let prime = match header.split_whitespace().collect::<Vec<_>>()[..] {
+            ["pratt", prime] => prime
+                .parse()
+                .map_err(|_| error(header_line, "invalid prime"))?,
+            _ => return Err(error(header_line, "expected `pratt <prime>`")),
+        };
+
+        let mut steps = Vec::new();
+        for (line, text) in lines {
+            let numbers = text
+                .split_whitespace()
+                .map(str::parse)
+                .collect::<Result<Vec<u64>, _>>()
+                .map_err(|_| error(line, "invalid number"))?;
+            if numbers.len() < 2 {
+                return Err(error(line, "expected `prime witness factors...`"));
+            }
+
+            steps.push(PrattStep {
+                prime: numbers[0],
+                witness: numbers[1],
+                factors: numbers[2..].to_vec(),
+            });
+        }
+
+        Ok(Certificate { prime, steps })
+    }
+}
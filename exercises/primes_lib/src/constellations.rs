@@ -0,0 +1,147 @@
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::math_utils::Primes;
+
+/// Prime k-tuples: yields every prime `p` in the range for which `p + offset`
+/// is prime for every offset of the pattern (the tuple may end past the range).
+///
+/// Primes come from one [`Primes`] iterator; a window of the primes in
+/// `p..=p + span` is kept, so every candidate is checked with binary searches.
+pub struct Constellations {
+    pattern: Vec<u64>,
+    primes: Primes,
+    window: VecDeque<u64>,
+    end: u64,
+    exhausted: bool,
+}
+
+impl Constellations {
+    /// # Panics
+    ///
+    /// Panics if the pattern does not start with `0` or is not strictly increasing.
+    pub fn new(range: Range<u64>, pattern: &[u64]) -> Constellations {
+        assert!(
+            pattern.first() == Some(&0),
+            "a constellation pattern starts with offset 0"
+        );
+        assert!(
+            pattern.windows(2).all(|w| w[0] < w[1]),
+            "constellation offsets must be strictly increasing"
+        );
+
+        Constellations {
+            pattern: pattern.to_vec(),
+            primes: Primes::starting_at(range.start),
+            window: VecDeque::new(),
+            end: range.end,
+            exhausted: false,
+        }
+    }
+
+    pub fn pattern(&self) -> &[u64] {
+        &self.pattern
+    }
+
+    fn span(&self) -> u64 {
+        *self.pattern.last().unwrap()
+    }
+
+    /// Makes sure the window holds every prime up to `limit`.
+    fn fill_window(&mut self, limit: u64) {
+        while !self.exhausted && self.window.back().is_none_or(|&last| last < limit) {
+            match self.primes.next() {
+                Some(p) => self.window.push_back(p),
+                None => self.exhausted = true,
+            }
+        }
+    }
+}
+
+impl Iterator for Constellations {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        loop {
+            self.fill_window(0);
+            let p = self.window.pop_front()?;
+            if p >= self.end {
+                return None;
+            }
+
+            let span = self.span();
+            self.fill_window(p.saturating_add(span));
+
+            let matches = self.pattern[1..].iter().all(|&offset| {
+                p.checked_add(offset)
+                    .is_some_and(|q| self.window.binary_search(&q).is_ok())
+            });
+            if matches {
+                return Some(p);
+            }
+        }
+    }
+}
+
+/// Twin primes `(p, p + 2)` with `p` in `range`.
+pub fn twin_primes(range: Range<u64>) -> impl Iterator<Item = (u64, u64)> {
+    Constellations::new(range, &[0, 2]).map(|p| (p, p + 2))
+}
+
+/// Cousin primes `(p, p + 4)` with `p` in `range`.
+pub fn cousin_primes(range: Range<u64>) -> impl Iterator<Item = (u64, u64)> {
+    Constellations::new(range, &[0, 4]).map(|p| (p, p + 4))
+}
+
+/// Sexy primes `(p, p + 6)` with `p` in `range`.
+pub fn sexy_primes(range: Range<u64>) -> impl Iterator<Item = (u64, u64)> {
+    Constellations::new(range, &[0, 6]).map(|p| (p, p + 6))
+}
+
+/// Two consecutive primes with no prime between them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrimeGap {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl PrimeGap {
+    pub fn len(&self) -> u64 {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+/// Every gap between consecutive primes in `range`, in ascending order.
+pub fn prime_gaps(range: Range<u64>) -> impl Iterator<Item = PrimeGap> {
+    let mut primes = Primes::in_range(range);
+    let mut previous = primes.next();
+
+    primes.map(move |p| {
+        let gap = PrimeGap {
+            start: previous.unwrap(),
+            end: p,
+        };
+        previous = Some(p);
+        gap
+    })
+}
+
+/// Maximal gaps in `range` - every gap longer than all gaps before it, reported
+/// where such a length first occurs.
+pub fn maximal_gaps(range: Range<u64>) -> impl Iterator<Item = PrimeGap> {
+    let mut record = 0;
+
+    prime_gaps(range).filter(move |gap| {
+        if gap.len() > record {
+            record = gap.len();
+            true
+        } else {
+            false
+        }
+    })
+}
@@ -0,0 +1,161 @@
+use alloc::vec::Vec;
+
+use crate::error::PrimesError;
+use crate::math_utils::Primes;
+use crate::sieve;
+
+/// Below this index the first primes are simply sieved.
+const NTH_PRIME_SIEVE_LIMIT: u64 = 100_000;
+
+/// Largest `x` accepted by [`prime_pi`]. The two tables of the count take
+/// 16 * sqrt(x) bytes - 160 MB here - and the count itself about a minute.
+pub const PRIME_PI_LIMIT: u64 = 100_000_000_000_000;
+
+/// Largest `n` accepted by [`nth_prime`]: pi([`PRIME_PI_LIMIT`]).
+pub const NTH_PRIME_LIMIT: u64 = 3_204_941_750_802;
+
+/// Number of primes `<= x`, computed with the Lucy_Hedgehog algorithm in
+/// O(x^(3/4)) time and O(sqrt(x)) memory - no primes are enumerated.
+///
+/// # Panics
+///
+/// Panics if `x > PRIME_PI_LIMIT` - see [`try_prime_pi`].
+pub fn prime_pi(x: u64) -> u64 {
+    try_prime_pi(x).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Checked [`prime_pi`].
+///
+/// Fails with [`PrimesError::LimitTooLarge`] if `x > PRIME_PI_LIMIT`.
+pub fn try_prime_pi(x: u64) -> Result<u64, PrimesError> {
+    if x > PRIME_PI_LIMIT {
+        return Err(PrimesError::LimitTooLarge {
+            limit: x as u128,
+            max: PRIME_PI_LIMIT as u128,
+        });
+    }
+    if x < 2 {
+        return Ok(0);
+    }
+
+    // every value of x / k takes one of about 2 * sqrt(x) distinct values:
+    // small[v] holds the count for v <= r, large[k] the count for x / k
+    let r = x.isqrt();
+    let mut small: Vec<u64> = (0..=r).map(|v| v.saturating_sub(1)).collect();
+    let mut large: Vec<u64> = (0..=r)
+        .map(|k| x.checked_div(k).map_or(0, |v| v - 1))
+        .collect();
+
+    for p in 2..=r {
+        if small[p as usize] == small[p as usize - 1] {
+            continue;
+        }
+
+        // p is prime - remove the numbers whose smallest prime factor is p
+        let primes_below = small[p as usize - 1];
+        let square = p * p;
+
+        for k in 1..=r.min(x / square) {
+            let v = x / k;
+            let count_v_over_p = match k.checked_mul(p) {
+                Some(kp) if kp <= r => large[kp as usize],
+                _ => small[(v / p) as usize],
+            };
+            large[k as usize] -= count_v_over_p - primes_below;
+        }
+
+        for v in (square..=r).rev() {
+            small[v as usize] -= small[(v / p) as usize] - primes_below;
+        }
+    }
+
+    Ok(large[1])
+}
+
+/// The `n`-th prime, counting from `nth_prime(1) == 2`.
+///
+/// The count of primes up to an estimate just below the answer comes from
+/// [`prime_pi`], so only the primes between the estimate and the answer are sieved.
+///
+/// # Panics
+///
+/// Panics if `n == 0` or `n > NTH_PRIME_LIMIT` - see [`try_nth_prime`].
+pub fn nth_prime(n: u64) -> u64 {
+    try_nth_prime(n).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Checked [`nth_prime`].
+///
+/// Fails with [`PrimesError::ZeroIndex`] if `n == 0` and with
+/// [`PrimesError::LimitTooLarge`] if `n > NTH_PRIME_LIMIT`.
+pub fn try_nth_prime(n: u64) -> Result<u64, PrimesError> {
+    if n == 0 {
+        return Err(PrimesError::ZeroIndex);
+    }
+    if n > NTH_PRIME_LIMIT {
+        return Err(PrimesError::LimitTooLarge {
+            limit: n as u128,
+            max: NTH_PRIME_LIMIT as u128,
+        });
+    }
+
+    if n <= NTH_PRIME_SIEVE_LIMIT {
+        return Ok(sieve::sieve_first(n as usize)[n as usize - 1]);
+    }
+
+    // start just below the answer and sieve forward from there
+    let mut start = estimate_nth_prime(n);
+    start -= start >> 16;
+    let mut count = prime_pi(start);
+    while count >= n {
+        // the margin was too small; never seen for n <= NTH_PRIME_LIMIT
+        start -= start >> 10;
+        count = prime_pi(start);
+    }
+    Ok(Primes::starting_at(start + 1)
+        .nth((n - count - 1) as usize)
+        .unwrap())
+}
+
+/// Cipolla's asymptotic expansion
+/// p_n ~ n (ln n + ln ln n - 1 + (ln ln n - 2) / ln n - ((ln ln n)^2 - 6 ln ln n + 11) / (2 ln^2 n)),
+/// evaluated in 32.32 fixed point - no floating point is needed.
+///
+/// From n = 10^7 on it is within 10^-5 of the answer, a little above it.
+fn estimate_nth_prime(n: u64) -> u64 {
+    const ONE: i128 = 1 << 32;
+
+    let ln_n = ln_fixed(n) as i128;
+    // ln(ln n) = ln(ln_n * 2^-32)
+    let ln_ln_n = ln_fixed(ln_n as u64) as i128 - 32 * LN_2_FIXED as i128;
+
+    let third = ((ln_ln_n - 2 * ONE) << 32) / ln_n;
+    let square = (ln_ln_n * ln_ln_n) >> 32;
+    let fourth = ((((square - 6 * ln_ln_n + 11 * ONE) << 32) / ln_n) << 32) / ln_n / 2;
+    let factor = ln_n + ln_ln_n - ONE + third - fourth;
+    u64::try_from((n as i128 * factor) >> 32).unwrap_or(u64::MAX)
+}
+
+/// ln 2 in 32.32 fixed point.
+const LN_2_FIXED: u64 = 2_977_044_472;
+
+/// ln x in 32.32 fixed point for `x >= 1`.
+///
+/// The integer part of log2 x is the position of the top bit; each further bit of the
+/// fraction comes from squaring the mantissa in `[1, 2)` and checking whether it reached 2.
+fn ln_fixed(x: u64) -> u64 {
+    let int = x.ilog2();
+    // mantissa with 63 fraction bits
+    let mut mantissa = (x as u128) << (63 - int);
+    let mut fraction = 0u64;
+    for bit in (0..32).rev() {
+        mantissa = (mantissa * mantissa) >> 63;
+        if mantissa >= 2 << 63 {
+            mantissa >>= 1;
+            fraction |= 1 << bit;
+        }
+    }
+
+    let log2 = (int as u64) << 32 | fraction;
+    ((log2 as u128 * LN_2_FIXED as u128) >> 32) as u64
+}
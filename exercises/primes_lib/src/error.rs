@@ -0,0 +1,35 @@
+use core::fmt;
+
+/// Errors reported by the checked (`try_`) variants of the functions in this crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrimesError {
+    /// The `count`-th prime is larger than `max`, the largest value of the target type.
+    Overflow { count: usize, max: u128 },
+    /// `limit` is beyond the largest input the crate supports, `max`.
+    LimitTooLarge { limit: u128, max: u128 },
+    /// The range contains no integers at all.
+    EmptyRange,
+    /// Primes are counted from 1 - there is no 0-th prime.
+    ZeroIndex,
+}
+
+impl fmt::Display for PrimesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrimesError::Overflow { count, max } => {
+                write!(f, "prime number {} is larger than {}", count, max)
+            }
+            PrimesError::LimitTooLarge { limit, max } => {
+                write!(
+                    f,
+                    "limit {} is larger than the supported maximum {}",
+                    limit, max
+                )
+            }
+            PrimesError::EmptyRange => write!(f, "the range is empty"),
+            PrimesError::ZeroIndex => write!(f, "primes are counted from nth_prime(1) == 2"),
+        }
+    }
+}
+
+impl core::error::Error for PrimesError {}
@@ -0,0 +1,127 @@
+use alloc::vec::Vec;
+
+use crate::math_utils::is_prime_fast;
+use crate::modular::{gcd, mod_mul};
+use crate::small_primes::primes_below;
+
+/// Factors below this bound are removed by trial division before Pollard's rho starts.
+const TRIAL_DIVISION_BOUND: u32 = 1 << 12;
+
+/// Number of steps whose differences are multiplied together before a single gcd.
+const BRENT_BATCH: u64 = 128;
+
+/// Prime factorization of `n` as `(prime, exponent)` pairs sorted by prime.
+///
+/// `0` and `1` have no prime factors - an empty vector is returned for both.
+pub fn factorize(n: u64) -> Vec<(u64, u32)> {
+    let mut factors = Vec::new();
+    if n < 2 {
+        return factors;
+    }
+
+    let mut rest = n;
+    for p in primes_below(TRIAL_DIVISION_BOUND) {
+        let p = p as u64;
+        if p * p > rest {
+            break;
+        }
+
+        let mut exponent = 0;
+        while rest.is_multiple_of(p) {
+            rest /= p;
+            exponent += 1;
+        }
+        if exponent > 0 {
+            factors.push((p, exponent));
+        }
+    }
+
+    let mut large_primes = Vec::new();
+    if rest > 1 {
+        split(rest, &mut large_primes);
+    }
+    large_primes.sort_unstable();
+
+    for p in large_primes {
+        match factors.last_mut() {
+            Some((last, exponent)) if *last == p => *exponent += 1,
+            _ => factors.push((p, 1)),
+        }
+    }
+
+    factors
+}
+
+/// Pushes every prime factor of `n` (with repetitions) onto `primes`.
+fn split(n: u64, primes: &mut Vec<u64>) {
+    if n == 1 {
+        return;
+    }
+    if is_prime_fast(n) {
+        primes.push(n);
+        return;
+    }
+
+    let divisor = pollard_brent(n);
+    split(divisor, primes);
+    split(n / divisor, primes);
+}
+
+/// Finds a non-trivial divisor of the odd composite `n` with Pollard's rho,
+/// using Brent's cycle detection and batched gcds.
+fn pollard_brent(n: u64) -> u64 {
+    if n.is_multiple_of(2) {
+        return 2;
+    }
+    let root = n.isqrt();
+    if root * root == n {
+        return root;
+    }
+
+    for c in 1.. {
+        let f = |x: u64| ((x as u128 * x as u128 + c as u128) % n as u128) as u64;
+
+        let mut y = 2;
+        let mut x = y;
+        let mut saved = y;
+        let mut product = 1;
+        let mut divisor = 1;
+        let mut cycle = 1;
+
+        while divisor == 1 {
+            x = y;
+            for _ in 0..cycle {
+                y = f(y);
+            }
+
+            let mut step = 0;
+            while step < cycle && divisor == 1 {
+                saved = y;
+                for _ in 0..BRENT_BATCH.min(cycle - step) {
+                    y = f(y);
+                    product = mod_mul(product, x.abs_diff(y), n);
+                }
+                divisor = gcd(product, n);
+                step += BRENT_BATCH;
+            }
+            cycle *= 2;
+        }
+
+        if divisor == n {
+            // the batch overshot - replay it one step at a time
+            loop {
+                saved = f(saved);
+                divisor = gcd(x.abs_diff(saved), n);
+                if divisor > 1 {
+                    break;
+                }
+            }
+        }
+
+        if divisor != n {
+            return divisor;
+        }
+    }
+
+    unreachable!("every odd composite has a factor found by some polynomial x^2 + c")
+}
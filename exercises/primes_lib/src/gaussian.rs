@@ -0,0 +1,242 @@
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Add, Mul, Neg, Sub};
+
+use crate::factor::factorize;
+use crate::math_utils::is_prime;
+use crate::modular::sqrt_mod;
+
+/// Gaussian integer `re + im * i`.
+///
+/// Arithmetic overflows like the underlying `i64` operations; the norm is
+/// computed in 128 bits and never overflows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GaussianInt {
+    pub re: i64,
+    pub im: i64,
+}
+
+impl GaussianInt {
+    pub const ZERO: GaussianInt = GaussianInt::new(0, 0);
+    pub const ONE: GaussianInt = GaussianInt::new(1, 0);
+    pub const I: GaussianInt = GaussianInt::new(0, 1);
+
+    pub const fn new(re: i64, im: i64) -> GaussianInt {
+        GaussianInt { re, im }
+    }
+
+    /// `re^2 + im^2`.
+    pub fn norm(self) -> u128 {
+        let re = self.re.unsigned_abs() as u128;
+        let im = self.im.unsigned_abs() as u128;
+        re * re + im * im
+    }
+
+    pub fn conj(self) -> GaussianInt {
+        GaussianInt::new(self.re, -self.im)
+    }
+
+    pub fn is_zero(self) -> bool {
+        self == GaussianInt::ZERO
+    }
+
+    /// `true` for `1`, `-1`, `i` and `-i`.
+    pub fn is_unit(self) -> bool {
+        self.norm() == 1
+    }
+
+    /// The associate in the first quadrant (`re > 0`, `im >= 0`) - the canonical
+    /// representative of `self` up to multiplication by a unit.
+    pub fn normalized(self) -> GaussianInt {
+        let mut z = self;
+        if z.is_zero() {
+            return z;
+        }
+        // rotate by i until z lies in the first quadrant
+        while z.re <= 0 || z.im < 0 {
+            z = z * GaussianInt::I;
+        }
+        z
+    }
+
+    /// Euclidean division rounding the exact quotient to the nearest Gaussian
+    /// integer, so that `norm(remainder) <= norm(divisor) / 2`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisor` is zero.
+    pub fn div_rem(self, divisor: GaussianInt) -> (GaussianInt, GaussianInt) {
+        assert!(!divisor.is_zero(), "attempt to divide by zero");
+
+        // self / divisor = self * conj(divisor) / norm(divisor)
+        let (a, b) = (self.re as i128, self.im as i128);
+        let (c, d) = (divisor.re as i128, divisor.im as i128);
+        let norm = divisor.norm() as i128;
+
+        let round = |numerator: i128| {
+            let (q, r) = (numerator.div_euclid(norm), numerator.rem_euclid(norm));
+            if r >= norm - r {
+                q + 1
+            } else {
+                q
+            }
+        };
+        let quotient = GaussianInt::new(round(a * c + b * d) as i64, round(b * c - a * d) as i64);
+        (quotient, self - quotient * divisor)
+    }
+
+    /// `Some(self / divisor)` if the division is exact.
+    pub fn checked_div(self, divisor: GaussianInt) -> Option<GaussianInt> {
+        if divisor.is_zero() {
+            return None;
+        }
+        let (quotient, remainder) = self.div_rem(divisor);
+        remainder.is_zero().then_some(quotient)
+    }
+
+    /// Greatest common divisor, normalized to the first quadrant.
+    pub fn gcd(self, other: GaussianInt) -> GaussianInt {
+        let (mut a, mut b) = (self, other);
+        while !b.is_zero() {
+            (a, b) = (b, a.div_rem(b).1);
+        }
+        a.normalized()
+    }
+}
+
+impl From<i64> for GaussianInt {
+    fn from(re: i64) -> GaussianInt {
+        GaussianInt::new(re, 0)
+    }
+}
+
+impl Add for GaussianInt {
+    type Output = GaussianInt;
+
+    fn add(self, other: GaussianInt) -> GaussianInt {
+        GaussianInt::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl Sub for GaussianInt {
+    type Output = GaussianInt;
+
+    fn sub(self, other: GaussianInt) -> GaussianInt {
+        GaussianInt::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl Mul for GaussianInt {
+    type Output = GaussianInt;
+
+    fn mul(self, other: GaussianInt) -> GaussianInt {
+        GaussianInt::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+impl Neg for GaussianInt {
+    type Output = GaussianInt;
+
+    fn neg(self) -> GaussianInt {
+        GaussianInt::new(-self.re, -self.im)
+    }
+}
+
+/// Formats as `3+2i`, `3-2i`, `-i`, `5` and so on.
+impl fmt::Display for GaussianInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let imaginary = match self.im {
+            1 => "i".to_string(),
+            -1 => "-i".to_string(),
+            im => format!("{}i", im),
+        };
+        match (self.re, self.im) {
+            (re, 0) => write!(f, "{}", re),
+            (0, _) => write!(f, "{}", imaginary),
+            (re, im) if im > 0 => write!(f, "{}+{}", re, imaginary),
+            (re, _) => write!(f, "{}{}", re, imaginary),
+        }
+    }
+}
+
+/// Primality in `Z[i]`.
+///
+/// `a + bi` with `a, b != 0` is prime iff its norm is a rational prime; `a` or
+/// `ai` is prime iff `|a|` is a rational prime `= 3 (mod 4)`.
+pub fn is_gaussian_prime(z: GaussianInt) -> bool {
+    match (z.re.unsigned_abs(), z.im.unsigned_abs()) {
+        (0, n) | (n, 0) => n % 4 == 3 && is_prime(n),
+        _ => is_prime(z.norm()),
+    }
+}
+
+/// Factorization into Gaussian primes, `(unit, factors)` with
+/// `z = unit * product(p^e)`.
+///
+/// The primes are normalized to the first quadrant and sorted by norm, then by
+/// real part. `0` returns `(0, [])`, units return `(z, [])`.
+///
+/// # Panics
+///
+/// Panics if the norm of `z` does not fit in `u64`.
+pub fn factorize_gaussian(z: GaussianInt) -> (GaussianInt, Vec<(GaussianInt, u32)>) {
+    let norm = u64::try_from(z.norm()).expect("the norm must fit in u64 to be factorized");
+    let mut factors = Vec::new();
+    if norm == 0 {
+        return (z, factors);
+    }
+
+    let mut rest = z;
+    // every Gaussian prime divides exactly one rational prime p, and its norm is p or p^2
+    for (p, exponent) in factorize(norm) {
+        let primes = if p == 2 {
+            vec![GaussianInt::new(1, 1)]
+        } else if p % 4 == 3 {
+            vec![GaussianInt::from(p as i64)]
+        } else {
+            let pi = sum_of_two_squares(p);
+            vec![pi, pi.conj().normalized()]
+        };
+
+        let mut remaining = exponent;
+        for pi in primes {
+            let mut count = 0;
+            while remaining > 0 {
+                match rest.checked_div(pi) {
+                    Some(quotient) => {
+                        rest = quotient;
+                        count += 1;
+                        remaining -= pi.norm().ilog(p as u128);
+                    }
+                    None => break,
+                }
+            }
+            if count > 0 {
+                factors.push((pi, count));
+            }
+        }
+    }
+
+    factors.sort_by_key(|&(pi, _)| (pi.norm(), pi.re));
+    (rest, factors)
+}
+
+/// `a + bi` with `a^2 + b^2 = p` for a prime `p = 1 (mod 4)` (Hermite-Serret).
+fn sum_of_two_squares(p: u64) -> GaussianInt {
+    // x^2 = -1 (mod p); the Euclidean algorithm on (p, x) passes through a
+    // remainder a < sqrt(p) with p - a^2 a square
+    let x = sqrt_mod(p - 1, p).unwrap();
+    let root = p.isqrt();
+    let (mut a, mut b) = (p, x);
+    while a > root {
+        (a, b) = (b, a % b);
+    }
+    let rest = p - a * a;
+    GaussianInt::new(a as i64, rest.isqrt() as i64).normalized()
+}
@@ -1,3 +1,48 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+pub mod arith;
+pub mod big_primes;
+pub mod bigint;
+pub mod certificate;
+pub mod constellations;
+pub mod counting;
+pub mod error;
+pub mod factor;
+pub mod gaussian;
+mod lucas;
 pub mod math_utils;
+pub mod modular;
+pub mod montgomery;
+#[cfg(feature = "std")]
+pub mod parallel;
+pub mod polynomial;
+pub mod prime_int;
+pub mod prime_set;
+pub mod random;
+pub mod sieve;
+pub mod small_primes;
+pub mod special;
 
-mod test_math_utils;
\ No newline at end of file
+mod test_arith;
+mod test_big_primes;
+mod test_bigint;
+mod test_certificate;
+mod test_constellations;
+mod test_counting;
+mod test_factor;
+mod test_gaussian;
+mod test_lucas;
+mod test_math_utils;
+mod test_modular;
+mod test_montgomery;
+#[cfg(feature = "std")]
+mod test_parallel;
+mod test_polynomial;
+mod test_prime_set;
+#[cfg(feature = "std")]
+mod test_properties;
+mod test_sieve;
+mod test_small_primes;
+mod test_special;
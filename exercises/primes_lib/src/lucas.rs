@@ -0,0 +1,81 @@
+/// Arithmetic modulo an odd `n`, as needed by [`is_strong_lucas_probable_prime`].
+///
+/// Residues may use any representation (e.g. Montgomery form) as long as the
+/// operations agree on it.
+pub(crate) trait LucasModulus {
+    type Residue: Clone;
+
+    /// `v mod n` for a small signed `v`.
+    fn residue(&self, v: i64) -> Self::Residue;
+    fn add(&self, a: &Self::Residue, b: &Self::Residue) -> Self::Residue;
+    fn sub(&self, a: &Self::Residue, b: &Self::Residue) -> Self::Residue;
+    fn mul(&self, a: &Self::Residue, b: &Self::Residue) -> Self::Residue;
+    /// `a / 2 mod n`.
+    fn half(&self, a: &Self::Residue) -> Self::Residue;
+    fn is_zero(&self, a: &Self::Residue) -> bool;
+
+    /// Jacobi symbol `(a / n)`.
+    fn jacobi(&self, a: i64) -> i32;
+    fn is_square(&self) -> bool;
+    /// Number of bits of `n + 1`.
+    fn n_plus_one_bits(&self) -> u64;
+    /// Bit `i` of `n + 1`.
+    fn n_plus_one_bit(&self, i: u64) -> bool;
+}
+
+/// Strong Lucas test with Selfridge's parameters: the first D in 5, -7, 9, -11, ...
+/// with (D/n) = -1, P = 1, Q = (1 - D) / 4.
+pub(crate) fn is_strong_lucas_probable_prime<M: LucasModulus>(m: &M) -> bool {
+    // no D with (D/n) = -1 exists for a square
+    if m.is_square() {
+        return false;
+    }
+
+    let mut d: i64 = 5;
+    loop {
+        match m.jacobi(d) {
+            -1 => break,
+            // D shares a factor with n, which proves nothing only if n divides D
+            0 if !m.is_zero(&m.residue(d)) => return false,
+            _ => d = if d > 0 { -(d + 2) } else { -d + 2 },
+        }
+    }
+    let d_n = m.residue(d);
+    let q = m.residue((1 - d) / 4);
+
+    // n + 1 = k * 2^s with k odd
+    let s = (0..).find(|&i| m.n_plus_one_bit(i)).unwrap();
+    let k_bits = m.n_plus_one_bits() - s;
+
+    // U_1 = 1, V_1 = P = 1, Q^1 = Q
+    let one = m.residue(1);
+    let (mut u, mut v, mut q_k) = (one.clone(), one, q.clone());
+
+    for bit in (0..k_bits - 1).rev() {
+        // doubling: U_2j = U_j V_j, V_2j = V_j^2 - 2 Q^j
+        u = m.mul(&u, &v);
+        v = m.sub(&m.mul(&v, &v), &m.add(&q_k, &q_k));
+        q_k = m.mul(&q_k, &q_k);
+
+        if m.n_plus_one_bit(s + bit) {
+            // increment: U_j+1 = (P U_j + V_j) / 2, V_j+1 = (D U_j + P V_j) / 2
+            let next_u = m.half(&m.add(&u, &v));
+            let next_v = m.half(&m.add(&m.mul(&d_n, &u), &v));
+            u = next_u;
+            v = next_v;
+            q_k = m.mul(&q_k, &q);
+        }
+    }
+
+    if m.is_zero(&u) || m.is_zero(&v) {
+        return true;
+    }
+    for _ in 1..s {
+        v = m.sub(&m.mul(&v, &v), &m.add(&q_k, &q_k));
+        if m.is_zero(&v) {
+            return true;
+        }
+        q_k = m.mul(&q_k, &q_k);
+    }
+    false
+}
@@ -1,43 +1,278 @@
-pub fn is_prime(n: u64) -> bool {
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+use core::ops::{Bound, Range, RangeBounds};
+
+use crate::error::PrimesError;
+use crate::modular::{mod_mul, mod_pow};
+use crate::montgomery::is_prime_u128;
+use crate::prime_int::PrimeInt;
+use crate::sieve::{self, SegmentedSieve, StackSieve};
+use crate::small_primes::{is_small_prime, SMALL_PRIMES, WHEEL_GAPS, WHEEL_MODULUS};
+
+/// Below this bound trial division is cheaper than Miller-Rabin.
+const TRIAL_DIVISION_LIMIT: u64 = 1 << 20;
+
+/// pi(2^64) - the number of primes that fit in `u64`.
+const PRIMES_BELOW_2_64: u128 = 425_656_284_035_217_743;
+
+/// Largest `count` accepted by [`try_n_primes`]: pi(2^32), about 1.6 GB of `u64`.
+pub const TRY_N_PRIMES_LIMIT: usize = 203_280_221;
+
+/// Widest range accepted by [`try_primes_in_range`]: 2^32 numbers, which never
+/// hold more than [`TRY_N_PRIMES_LIMIT`] primes.
+pub const TRY_RANGE_WIDTH_LIMIT: u64 = 1 << 32;
+
+/// Bases that make Miller-Rabin deterministic for every `u64` (every n < 3.3 * 10^24).
+const MILLER_RABIN_WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Values that fit in `u64` are checked with [`is_prime_trial`] or [`is_prime_fast`]
+/// depending on their size, wider `u128` values with a Montgomery-based Baillie-PSW test.
+pub fn is_prime<T: PrimeInt>(n: T) -> bool {
+    let n = n.to_u128();
+    match u64::try_from(n) {
+        Ok(n) if n < TRIAL_DIVISION_LIMIT => is_prime_trial(n),
+        Ok(n) => is_prime_fast(n),
+        Err(_) => is_prime_u128(n),
+    }
+}
+
+/// Trial division up to `sqrt(n)`.
+///
+/// Values below 2^16 are looked up in a table. Larger ones are divided by the
+/// primes below 2^10 and then - past the end of that list - only by numbers
+/// coprime to 2 * 3 * 5 * 7, which skips 77% of the divisors.
+pub fn is_prime_trial(n: u64) -> bool {
+    if let Ok(n) = u16::try_from(n) {
+        return is_small_prime(n);
+    }
+
+    for p in SMALL_PRIMES {
+        let p = p as u64;
+        if p * p > n {
+            return true;
+        }
+        if n.is_multiple_of(p) {
+            return false;
+        }
+    }
+
+    // continue on the wheel from the first spoke past the last listed prime
+    let last_listed = *SMALL_PRIMES.last().unwrap() as u64;
+    let mut divisor = last_listed / WHEEL_MODULUS * WHEEL_MODULUS + 1;
+    for &gap in WHEEL_GAPS.iter().cycle() {
+        divisor += gap as u64;
+        if divisor <= last_listed {
+            continue;
+        }
+        if divisor > n / divisor {
+            return true;
+        }
+        if n.is_multiple_of(divisor) {
+            return false;
+        }
+    }
+    unreachable!()
+}
+
+/// Trial division by every integer up to `sqrt(n)` - the original implementation,
+/// kept as a baseline for the benchmarks.
+pub fn is_prime_trial_naive(n: u64) -> bool {
     if n <= 1 {
         return false;
     }
 
-    for i in 2..=(n as f64).sqrt() as u64 {
-        if n % i == 0 {
+    for i in 2..=n.isqrt() {
+        if n.is_multiple_of(i) {
             return false;
         }
     }
     true
 }
 
-pub fn n_primes(count: usize) -> Vec<u64> {
-    let mut result: Vec<u64> = Vec::new();
-    let mut candidate: u64 = 2;
+/// Deterministic Miller-Rabin test - correct for the whole `u64` domain.
+pub fn is_prime_fast(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
 
-    while result.len() < count {
-        if is_prime(candidate) {
-            result.push(candidate);
+    for p in MILLER_RABIN_WITNESSES {
+        if n.is_multiple_of(p) {
+            return n == p;
         }
-        candidate += 1;
     }
 
-    result
-}
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
 
-pub fn primes_in_range(range: std::ops::Range<u64>) -> Vec<u64> {
-    let mut result: Vec<u64> = Vec::new();
+    'witnesses: for a in MILLER_RABIN_WITNESSES {
+        let mut x = mod_pow(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
 
-    for value in range {
-        if is_prime(value) {
-            result.push(value);
+        for _ in 1..s {
+            x = mod_mul(x, x, n);
+            if x == n - 1 {
+                continue 'witnesses;
+            }
         }
+
+        return false;
+    }
+
+    true
+}
+
+/// # Panics
+///
+/// Panics if one of the first `count` primes does not fit in `T`.
+pub fn n_primes<T: PrimeInt>(count: usize) -> Vec<T> {
+    sieve::sieve_first(count)
+        .into_iter()
+        .map(|p| T::from_u128(p as u128).expect("prime does not fit in the target type"))
+        .collect()
+}
+
+/// Checked [`n_primes`].
+///
+/// Fails with [`PrimesError::Overflow`] if one of the primes does not fit in `T`,
+/// and with [`PrimesError::LimitTooLarge`] if `count` exceeds the number of primes
+/// below 2^64 - the sieve behind [`n_primes`] works in `u64` - or
+/// [`TRY_N_PRIMES_LIMIT`].
+pub fn try_n_primes<T: PrimeInt>(count: usize) -> Result<Vec<T>, PrimesError> {
+    let max = T::MAX.to_u128();
+    let available = match max {
+        0xFF => 54,
+        0xFFFF => 6_542,
+        0xFFFF_FFFF => 203_280_221,
+        _ => PRIMES_BELOW_2_64,
+    };
+
+    if count as u128 > available {
+        return Err(if max <= u64::MAX as u128 {
+            PrimesError::Overflow { count, max }
+        } else {
+            PrimesError::LimitTooLarge {
+                limit: count as u128,
+                max: PRIMES_BELOW_2_64,
+            }
+        });
+    }
+
+    if count > TRY_N_PRIMES_LIMIT {
+        return Err(PrimesError::LimitTooLarge {
+            limit: count as u128,
+            max: TRY_N_PRIMES_LIMIT as u128,
+        });
+    }
+
+    Ok(n_primes(count))
+}
+
+pub fn primes_in_range<T: PrimeInt>(range: Range<T>) -> Vec<T> {
+    let start = range.start.to_u128();
+    let end = range.end.to_u128().max(start);
+
+    // u64::MAX itself is composite, so the sieved part may stop right before it
+    let u64_end = u64::MAX as u128;
+    let mut primes: Vec<T> =
+        primes_in_range_u64(start.min(u64_end) as u64..end.min(u64_end) as u64)
+            .into_iter()
+            .map(|p| T::from_u128(p as u128).unwrap())
+            .collect();
+
+    let wide = start.max(u64_end)..end.max(u64_end);
+    primes.extend(
+        wide.filter(|&n| is_prime_u128(n))
+            .map(|n| T::from_u128(n).unwrap()),
+    );
+
+    primes
+}
+
+/// Checked variant of [`primes_in_range`] for any kind of `u64` range, including
+/// inclusive ones such as `..=u64::MAX`.
+///
+/// Fails with [`PrimesError::EmptyRange`] if the range contains no integers, and
+/// with [`PrimesError::LimitTooLarge`] if it is wider than [`TRY_RANGE_WIDTH_LIMIT`].
+pub fn try_primes_in_range<R: RangeBounds<u64>>(range: R) -> Result<Vec<u64>, PrimesError> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start as u128,
+        Bound::Excluded(&start) => start as u128 + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end as u128 + 1,
+        Bound::Excluded(&end) => end as u128,
+        Bound::Unbounded => u64::MAX as u128 + 1,
+    };
+
+    if start >= end {
+        return Err(PrimesError::EmptyRange);
+    }
+    if end - start > TRY_RANGE_WIDTH_LIMIT as u128 {
+        return Err(PrimesError::LimitTooLarge {
+            limit: end - start,
+            max: TRY_RANGE_WIDTH_LIMIT as u128,
+        });
+    }
+
+    // u64::MAX is composite, so an end past it can be clamped
+    let u64_end = u64::MAX as u128;
+    Ok(primes_in_range_u64(
+        start.min(u64_end) as u64..end.min(u64_end) as u64,
+    ))
+}
+
+fn primes_in_range_u64(range: Range<u64>) -> Vec<u64> {
+    // sieving needs every base prime up to sqrt(end) - for a narrow window
+    // high up it is cheaper to test the candidates one by one
+    let width = range.end.saturating_sub(range.start);
+    if width < range.end.saturating_sub(1).isqrt() {
+        return range.filter(|&n| is_prime_fast(n)).collect();
+    }
+
+    sieve::sieve_range(range)
+}
+
+/// Writes the primes in `range` into `out` in ascending order without allocating,
+/// and returns how many were written.
+///
+/// Stops early when `out` is full; continue from one past the last written prime
+/// to get the rest.
+pub fn primes_in_range_into(range: Range<u64>, out: &mut [u64]) -> usize {
+    fill_from(StackSieve::new(range), out)
+}
+
+/// Writes the first `out.len()` primes into `out` without allocating, and returns
+/// how many were written - all of them unless `out` has room for more than pi(2^64).
+pub fn n_primes_into(out: &mut [u64]) -> usize {
+    fill_from(StackSieve::new(0..u64::MAX), out)
+}
+
+fn fill_from(primes: impl Iterator<Item = u64>, out: &mut [u64]) -> usize {
+    let mut written = 0;
+    // `out` goes first so that no prime is sieved past the last slot
+    for (slot, p) in out.iter_mut().zip(primes) {
+        *slot = p;
+        written += 1;
     }
+    written
+}
+
+/// Smallest prime `> n`, `None` if it does not fit in `u64`.
+pub fn next_prime_after(n: u64) -> Option<u64> {
+    let from = n.checked_add(1)?;
+    (from..=u64::MAX).find(|&candidate| is_prime(candidate))
+}
 
-    return result;
+/// Largest prime `< n`, `None` for `n <= 2`.
+pub fn prev_prime_before(n: u64) -> Option<u64> {
+    (2..n).rev().find(|&candidate| is_prime(candidate))
 }
 
-pub fn primes_from_vec(range: Vec<u64>) -> Vec<u64> {
+pub fn primes_from_vec<T: PrimeInt>(range: Vec<T>) -> Vec<T> {
     let mut primes = Vec::new();
 
     for num in range {
@@ -47,4 +282,90 @@ pub fn primes_from_vec(range: Vec<u64>) -> Vec<u64> {
     }
 
     primes
-}
\ No newline at end of file
+}
+
+/// Lazy iterator over primes in ascending order.
+///
+/// `next` sieves forward one segment at a time, `next_back` steps down from the
+/// end of the range with [`is_prime`] - both ends meet without yielding a prime twice.
+///
+/// Buffers each segment on the heap; [`StackSieve`] is the forward-only variant
+/// that does not allocate.
+pub struct Primes {
+    sieve: SegmentedSieve,
+    buffer: VecDeque<u64>,
+    back: u64,
+}
+
+impl Primes {
+    /// All primes representable as `u64`.
+    pub fn new() -> Primes {
+        Primes::in_range(0..u64::MAX)
+    }
+
+    /// Primes `>= start`.
+    pub fn starting_at(start: u64) -> Primes {
+        Primes::in_range(start..u64::MAX)
+    }
+
+    pub fn in_range(range: Range<u64>) -> Primes {
+        Primes {
+            back: range.end.max(range.start),
+            sieve: SegmentedSieve::new(range),
+            buffer: VecDeque::new(),
+        }
+    }
+}
+
+impl Default for Primes {
+    fn default() -> Self {
+        Primes::new()
+    }
+}
+
+impl Iterator for Primes {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        loop {
+            if let Some(p) = self.buffer.pop_front() {
+                if p < self.back {
+                    return Some(p);
+                }
+                self.buffer.clear();
+                return None;
+            }
+
+            if self.sieve.position() >= self.back || !self.sieve.next_segment(&mut self.buffer) {
+                return None;
+            }
+        }
+    }
+}
+
+impl DoubleEndedIterator for Primes {
+    fn next_back(&mut self) -> Option<u64> {
+        // candidates the forward sieve has not reached yet
+        while self.back > self.sieve.position() {
+            self.back -= 1;
+            if is_prime(self.back) {
+                return Some(self.back);
+            }
+        }
+
+        while let Some(p) = self.buffer.pop_back() {
+            if p < self.back {
+                self.back = p;
+                return Some(p);
+            }
+        }
+        None
+    }
+}
+
+impl FusedIterator for Primes {}
+
+/// Infinite (up to `u64::MAX`) iterator over all primes.
+pub fn all_primes() -> Primes {
+    Primes::new()
+}
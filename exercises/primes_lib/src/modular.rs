@@ -0,0 +1,172 @@
+use crate::math_utils::is_prime;
+
+/// a * b mod m without overflow - the product is computed in 128 bits.
+pub fn mod_mul(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128 * b as u128) % m as u128) as u64
+}
+
+/// base^exp mod m by square-and-multiply.
+///
+/// # Panics
+///
+/// Panics if `m == 0`.
+pub fn mod_pow(mut base: u64, mut exp: u64, m: u64) -> u64 {
+    let mut result = 1 % m;
+    base %= m;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mod_mul(result, base, m);
+        }
+        base = mod_mul(base, base, m);
+        exp >>= 1;
+    }
+
+    result
+}
+
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// # Panics
+///
+/// Panics if the result does not fit in `u64`.
+pub fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd(a, b)).checked_mul(b).expect("lcm overflows u64")
+}
+
+/// Extended Euclid - returns `(g, x, y)` with `g = gcd(a, b) = a * x + b * y`.
+pub fn extended_gcd(a: u64, b: u64) -> (u64, i128, i128) {
+    let (mut old_r, mut r) = (a as i128, b as i128);
+    let (mut old_x, mut x) = (1i128, 0i128);
+    let (mut old_y, mut y) = (0i128, 1i128);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    (old_r as u64, old_x, old_y)
+}
+
+/// Inverse of `a` modulo `m`, `None` if `gcd(a, m) != 1` or `m == 0`.
+pub fn mod_inv(a: u64, m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+
+    let (g, x, _) = extended_gcd(a % m, m);
+    if g != 1 {
+        return None;
+    }
+    Some(x.rem_euclid(m as i128) as u64)
+}
+
+/// Solves `x = a1 (mod m1)`, `x = a2 (mod m2)` for moduli that need not be coprime.
+///
+/// Returns `(x, lcm(m1, m2))` with `0 <= x < lcm(m1, m2)`, or `None` if the system
+/// has no solution, a modulus is `0`, or the combined modulus does not fit in `u64`.
+pub fn crt_pair(a1: u64, m1: u64, a2: u64, m2: u64) -> Option<(u64, u64)> {
+    if m1 == 0 || m2 == 0 {
+        return None;
+    }
+    let (a1, a2) = (a1 % m1, a2 % m2);
+
+    let g = gcd(m1, m2);
+    let diff = a2 as i128 - a1 as i128;
+    if diff % g as i128 != 0 {
+        return None;
+    }
+
+    let m2_reduced = m2 / g;
+    let modulus = m1.checked_mul(m2_reduced)?;
+
+    // a1 + m1 * k solves the second congruence for k = (diff / g) * (m1 / g)^-1 mod (m2 / g)
+    let inverse = mod_inv(m1 / g, m2_reduced)? as u128;
+    let steps = (diff / g as i128).rem_euclid(m2_reduced as i128) as u128;
+    let k = steps * inverse % m2_reduced as u128;
+
+    let x = (a1 as u128 + m1 as u128 * k) % modulus as u128;
+    Some((x as u64, modulus))
+}
+
+/// Chinese Remainder Theorem for any number of congruences `x = a (mod m)`.
+///
+/// Returns `(x, lcm of all moduli)`, see [`crt_pair`] for when `None` is returned.
+/// An empty system is solved by every integer: `Some((0, 1))`.
+pub fn crt(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    congruences
+        .iter()
+        .try_fold((0, 1), |(x, m), &(a, n)| crt_pair(x, m, a, n))
+}
+
+/// Square root of `a` modulo the prime `p` (Tonelli-Shanks).
+///
+/// Returns the smaller of the two roots `r` and `p - r`, or `None` if `a` is not
+/// a quadratic residue modulo `p`.
+///
+/// # Panics
+///
+/// Panics if `p` is not prime.
+pub fn sqrt_mod(a: u64, p: u64) -> Option<u64> {
+    assert!(is_prime(p), "sqrt_mod needs a prime modulus");
+
+    let a = a % p;
+    if p == 2 || a == 0 {
+        return Some(a);
+    }
+    // Euler's criterion
+    if mod_pow(a, (p - 1) / 2, p) != 1 {
+        return None;
+    }
+
+    let root = if p % 4 == 3 {
+        mod_pow(a, (p + 1) / 4, p)
+    } else {
+        // p - 1 = q * 2^s with q odd
+        let s = (p - 1).trailing_zeros();
+        let q = (p - 1) >> s;
+
+        let non_residue = (2..p)
+            .find(|&z| mod_pow(z, (p - 1) / 2, p) == p - 1)
+            .unwrap();
+
+        let mut m = s;
+        let mut c = mod_pow(non_residue, q, p);
+        let mut t = mod_pow(a, q, p);
+        let mut r = mod_pow(a, q.div_ceil(2), p);
+
+        while t != 1 {
+            // least i with t^(2^i) = 1
+            let mut i = 0;
+            let mut t_power = t;
+            while t_power != 1 {
+                t_power = mod_mul(t_power, t_power, p);
+                i += 1;
+            }
+
+            let mut b = c;
+            for _ in 0..(m - i - 1) {
+                b = mod_mul(b, b, p);
+            }
+
+            m = i;
+            c = mod_mul(b, b, p);
+            t = mod_mul(t, c, p);
+            r = mod_mul(r, b, p);
+        }
+
+        r
+    };
+
+    Some(root.min(p - root))
+}
@@ -0,0 +1,275 @@
+use crate::lucas::{self, LucasModulus};
+
+/// Modular arithmetic for an odd `u128` modulus in Montgomery form (R = 2^128).
+///
+/// Products of two `u128` values need 256 bits, so plain `(a * b) % n` is not an
+/// option - `mul` computes the full product and reduces it with REDC instead.
+#[derive(Debug, Clone, Copy)]
+pub struct Montgomery {
+    n: u128,
+    /// -n^(-1) mod 2^128
+    n_neg_inv: u128,
+    /// R^2 mod n
+    r2: u128,
+}
+
+impl Montgomery {
+    /// # Panics
+    ///
+    /// Panics if `n` is even or smaller than 3.
+    pub fn new(n: u128) -> Montgomery {
+        assert!(
+            n % 2 == 1 && n > 1,
+            "Montgomery modulus must be odd and > 1"
+        );
+
+        let mut m = Montgomery {
+            n,
+            n_neg_inv: inverse_mod_2_128(n).wrapping_neg(),
+            r2: 0,
+        };
+
+        // R mod n doubled 128 times gives R^2 mod n
+        let mut r2 = (u128::MAX % n + 1) % n;
+        for _ in 0..128 {
+            r2 = m.add(r2, r2);
+        }
+        m.r2 = r2;
+
+        m
+    }
+
+    pub fn modulus(&self) -> u128 {
+        self.n
+    }
+
+    pub fn to_mont(&self, a: u128) -> u128 {
+        self.mul(a % self.n, self.r2)
+    }
+
+    pub fn from_mont(&self, a: u128) -> u128 {
+        self.redc(0, a)
+    }
+
+    /// Montgomery form of 1.
+    pub fn one(&self) -> u128 {
+        self.to_mont(1)
+    }
+
+    pub fn add(&self, a: u128, b: u128) -> u128 {
+        let (sum, overflow) = a.overflowing_add(b);
+        if overflow || sum >= self.n {
+            sum.wrapping_sub(self.n)
+        } else {
+            sum
+        }
+    }
+
+    pub fn sub(&self, a: u128, b: u128) -> u128 {
+        if a >= b {
+            a - b
+        } else {
+            a.wrapping_sub(b).wrapping_add(self.n)
+        }
+    }
+
+    /// a / 2 mod n - the same operation in and out of Montgomery form.
+    pub fn half(&self, a: u128) -> u128 {
+        if a.is_multiple_of(2) {
+            a >> 1
+        } else {
+            (a >> 1) + (self.n >> 1) + 1
+        }
+    }
+
+    pub fn mul(&self, a: u128, b: u128) -> u128 {
+        let (hi, lo) = wide_mul(a, b);
+        self.redc(hi, lo)
+    }
+
+    /// base^exp for `base` in Montgomery form, result in Montgomery form.
+    pub fn pow(&self, mut base: u128, mut exp: u128) -> u128 {
+        let mut result = self.one();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = self.mul(result, base);
+            }
+            base = self.mul(base, base);
+            exp >>= 1;
+        }
+        result
+    }
+
+    /// base^exp mod n for plain (not Montgomery) values.
+    pub fn pow_mod(&self, base: u128, exp: u128) -> u128 {
+        self.from_mont(self.pow(self.to_mont(base), exp))
+    }
+
+    /// (hi * 2^128 + lo) * R^(-1) mod n, for inputs below n * R.
+    fn redc(&self, hi: u128, lo: u128) -> u128 {
+        let m = lo.wrapping_mul(self.n_neg_inv);
+        let (mn_hi, mn_lo) = wide_mul(m, self.n);
+
+        // lo + mn_lo is 0 mod 2^128 - only its carry survives
+        let carry = (lo.overflowing_add(mn_lo).1) as u128;
+        let (t, overflow1) = hi.overflowing_add(mn_hi);
+        let (t, overflow2) = t.overflowing_add(carry);
+
+        if overflow1 || overflow2 || t >= self.n {
+            t.wrapping_sub(self.n)
+        } else {
+            t
+        }
+    }
+}
+
+/// n^(-1) mod 2^128 for odd `n`.
+///
+/// Newton iteration - every step doubles the number of correct low bits, starting
+/// from the 3 bits of n itself (n * n = 1 mod 8). The low 64 bits are the inverse
+/// mod 2^64.
+pub(crate) fn inverse_mod_2_128(n: u128) -> u128 {
+    let mut inv = n;
+    for _ in 0..6 {
+        inv = inv.wrapping_mul(2u128.wrapping_sub(n.wrapping_mul(inv)));
+    }
+    inv
+}
+
+/// Full 256-bit product as (high, low) halves.
+fn wide_mul(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+
+    let (a0, a1) = (a & MASK, a >> 64);
+    let (b0, b1) = (b & MASK, b >> 64);
+
+    let p0 = a0 * b0;
+    let p1 = a0 * b1;
+    let p2 = a1 * b0;
+    let p3 = a1 * b1;
+
+    let mid = (p0 >> 64) + (p1 & MASK) + (p2 & MASK);
+    let lo = (p0 & MASK) | (mid << 64);
+    let hi = p3 + (p1 >> 64) + (p2 >> 64) + (mid >> 64);
+
+    (hi, lo)
+}
+
+/// Baillie-PSW test for `u128` - strong base-2 Miller-Rabin followed by a strong
+/// Lucas test. No counterexample is known, and there is none below 2^64.
+pub fn is_prime_u128(n: u128) -> bool {
+    if n < 2 {
+        return false;
+    }
+    for p in [2u128, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37] {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+
+    let m = Montgomery::new(n);
+    is_strong_probable_prime(&m, 2) && lucas::is_strong_lucas_probable_prime(&m)
+}
+
+fn is_strong_probable_prime(m: &Montgomery, base: u128) -> bool {
+    let n = m.modulus();
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+
+    let one = m.one();
+    let minus_one = m.sub(0, one);
+
+    let mut x = m.pow(m.to_mont(base), d);
+    if x == one || x == minus_one {
+        return true;
+    }
+    for _ in 1..s {
+        x = m.mul(x, x);
+        if x == minus_one {
+            return true;
+        }
+    }
+    false
+}
+
+impl LucasModulus for Montgomery {
+    type Residue = u128;
+
+    fn residue(&self, v: i64) -> u128 {
+        let magnitude = self.to_mont(v.unsigned_abs() as u128);
+        if v < 0 {
+            Montgomery::sub(self, 0, magnitude)
+        } else {
+            magnitude
+        }
+    }
+
+    fn add(&self, a: &u128, b: &u128) -> u128 {
+        Montgomery::add(self, *a, *b)
+    }
+
+    fn sub(&self, a: &u128, b: &u128) -> u128 {
+        Montgomery::sub(self, *a, *b)
+    }
+
+    fn mul(&self, a: &u128, b: &u128) -> u128 {
+        Montgomery::mul(self, *a, *b)
+    }
+
+    fn half(&self, a: &u128) -> u128 {
+        Montgomery::half(self, *a)
+    }
+
+    fn is_zero(&self, a: &u128) -> bool {
+        *a == 0
+    }
+
+    fn jacobi(&self, a: i64) -> i32 {
+        jacobi(a as i128, self.n)
+    }
+
+    fn is_square(&self) -> bool {
+        let root = self.n.isqrt();
+        root * root == self.n
+    }
+
+    fn n_plus_one_bits(&self) -> u64 {
+        // no overflow: u128::MAX is divisible by 3 and never gets this far
+        (128 - (self.n + 1).leading_zeros()) as u64
+    }
+
+    fn n_plus_one_bit(&self, i: u64) -> bool {
+        ((self.n + 1) >> i) & 1 == 1
+    }
+}
+
+/// Jacobi symbol (a / n) for odd n > 0.
+pub(crate) fn jacobi(a: i128, n: u128) -> i32 {
+    let mut a = if a < 0 {
+        (n - a.unsigned_abs() % n) % n
+    } else {
+        a as u128 % n
+    };
+    let mut n = n;
+    let mut result = 1;
+
+    while a != 0 {
+        while a.is_multiple_of(2) {
+            a /= 2;
+            if n % 8 == 3 || n % 8 == 5 {
+                result = -result;
+            }
+        }
+        (a, n) = (n, a);
+        if a % 4 == 3 && n % 4 == 3 {
+            result = -result;
+        }
+        a %= n;
+    }
+
+    if n == 1 {
+        result
+    } else {
+        0
+    }
+}
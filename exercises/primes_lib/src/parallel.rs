@@ -0,0 +1,105 @@
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+
+use multithreading::thread_pool::ThreadPool;
+
+use crate::math_utils::{is_prime, primes_in_range};
+use crate::prime_int::PrimeInt;
+
+/// Chunks per thread - a few more chunks than threads keeps the pool busy when
+/// chunks high up in the range take longer than the ones at the start.
+const CHUNKS_PER_THREAD: u128 = 4;
+
+/// Most worker threads one call starts, whatever `threads` asks for.
+pub const MAX_THREADS: u32 = 64;
+
+/// Ranges shorter than this are not worth splitting.
+const MIN_CHUNK_LEN: u128 = 1 << 16;
+
+/// Parallel [`primes_in_range`] - the range is split into chunks sieved on a
+/// [`ThreadPool`] with `threads` workers (at most [`MAX_THREADS`]), and the
+/// results are merged in order.
+///
+/// The pool is the one from the `multithreading` exercise, whose workers
+/// print a line on stdout for each task they take.
+pub fn par_primes_in_range<T: PrimeInt>(range: Range<T>, threads: u32) -> Vec<T> {
+    let start = range.start.to_u128();
+    let end = range.end.to_u128().max(start);
+
+    let threads = threads.clamp(1, MAX_THREADS);
+    let chunk_len = ((end - start) / (threads as u128 * CHUNKS_PER_THREAD)).max(MIN_CHUNK_LEN);
+
+    let mut chunks = Vec::new();
+    let mut low = start;
+    while low < end {
+        let high = low.saturating_add(chunk_len).min(end);
+        chunks.push(T::from_u128(low).unwrap()..T::from_u128(high).unwrap());
+        low = high;
+    }
+
+    run_chunks(chunks, threads, primes_in_range)
+}
+
+/// Parallel [`crate::math_utils::primes_from_vec`] - keeps the order of `values`.
+pub fn par_primes_from_vec<T: PrimeInt>(values: Vec<T>, threads: u32) -> Vec<T> {
+    let threads = threads.clamp(1, MAX_THREADS);
+    let chunk_len = values
+        .len()
+        .div_ceil(threads as usize * CHUNKS_PER_THREAD as usize)
+        .max(1);
+
+    let chunks = values
+        .chunks(chunk_len)
+        .map(|chunk| chunk.to_vec())
+        .collect();
+
+    run_chunks(chunks, threads, |chunk: Vec<T>| {
+        chunk.into_iter().filter(|&n| is_prime(n)).collect()
+    })
+}
+
+/// Runs `job` for every chunk on a thread pool and concatenates the results in chunk order.
+///
+/// A panic in `job` is caught on the worker, so the pool still shuts down cleanly,
+/// and then raised again here once every chunk is done.
+pub(crate) fn run_chunks<C, T, F>(chunks: Vec<C>, threads: u32, job: F) -> Vec<T>
+where
+    C: Send + 'static,
+    T: Send + 'static,
+    F: Fn(C) -> Vec<T> + Copy + Send + 'static,
+{
+    let count = chunks.len();
+    // no point in idle workers
+    let threads = threads.min(count.try_into().unwrap_or(u32::MAX)).max(1);
+
+    let pool = ThreadPool::new(threads);
+    let (sender, receiver) = mpsc::channel();
+
+    for (index, chunk) in chunks.into_iter().enumerate() {
+        let sender = sender.clone();
+        pool.execute(move || {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| job(chunk)));
+            // the receiver outlives every task
+            sender.send((index, result)).unwrap();
+        });
+    }
+    drop(sender);
+
+    let mut results: Vec<Vec<T>> = (0..count).map(|_| Vec::new()).collect();
+    let mut first_panic = None;
+    for (index, result) in receiver {
+        match result {
+            Ok(primes) => results[index] = primes,
+            Err(payload) => {
+                first_panic.get_or_insert(payload);
+            }
+        }
+    }
+    drop(pool);
+
+    if let Some(payload) = first_panic {
+        panic::resume_unwind(payload);
+    }
+    results.into_iter().flatten().collect()
+}
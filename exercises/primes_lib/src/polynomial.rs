@@ -0,0 +1,350 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Add, Mul, Neg, Sub};
+
+use crate::arith::mobius;
+use crate::factor::factorize;
+use crate::math_utils::is_prime;
+use crate::modular::{mod_inv, mod_mul};
+
+/// Polynomial with coefficients in the prime field `GF(p)`.
+///
+/// Coefficients are stored lowest degree first and without trailing zeros, so
+/// equal polynomials compare equal. Operations on polynomials over different
+/// fields panic.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Polynomial {
+    p: u64,
+    coefficients: Vec<u64>,
+}
+
+impl Polynomial {
+    /// Polynomial `c[0] + c[1] x + c[2] x^2 + ...` with every coefficient reduced modulo `p`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not prime.
+    pub fn new(coefficients: Vec<u64>, p: u64) -> Polynomial {
+        assert!(is_prime(p), "polynomial coefficients need a prime modulus");
+        let coefficients = coefficients.into_iter().map(|c| c % p).collect();
+        Polynomial::from_reduced(coefficients, p)
+    }
+
+    /// The zero polynomial over `GF(p)`.
+    pub fn zero(p: u64) -> Polynomial {
+        Polynomial::new(Vec::new(), p)
+    }
+
+    /// The polynomial `x` over `GF(p)`.
+    pub fn x(p: u64) -> Polynomial {
+        Polynomial::new(vec![0, 1], p)
+    }
+
+    fn from_reduced(mut coefficients: Vec<u64>, p: u64) -> Polynomial {
+        while coefficients.last() == Some(&0) {
+            coefficients.pop();
+        }
+        Polynomial { p, coefficients }
+    }
+
+    /// The characteristic of the coefficient field.
+    pub fn modulus(&self) -> u64 {
+        self.p
+    }
+
+    /// Coefficients lowest degree first, without trailing zeros.
+    pub fn coefficients(&self) -> &[u64] {
+        &self.coefficients
+    }
+
+    /// `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.is_empty()
+    }
+
+    pub fn is_monic(&self) -> bool {
+        self.coefficients.last() == Some(&1)
+    }
+
+    /// Value at `x`.
+    pub fn evaluate(&self, x: u64) -> u64 {
+        let x = x % self.p;
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(0, |acc, &c| add_mod(mod_mul(acc, x, self.p), c, self.p))
+    }
+
+    /// `self` divided by its leading coefficient; the zero polynomial stays zero.
+    pub fn monic(&self) -> Polynomial {
+        match self.coefficients.last() {
+            Some(&lead) => self.scale(mod_inv(lead, self.p).unwrap()),
+            None => self.clone(),
+        }
+    }
+
+    fn scale(&self, factor: u64) -> Polynomial {
+        let coefficients = self
+            .coefficients
+            .iter()
+            .map(|&c| mod_mul(c, factor, self.p))
+            .collect();
+        Polynomial::from_reduced(coefficients, self.p)
+    }
+
+    /// Polynomial long division - `(q, r)` with `self = q * divisor + r` and
+    /// `deg(r) < deg(divisor)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisor` is zero.
+    pub fn div_rem(&self, divisor: &Polynomial) -> (Polynomial, Polynomial) {
+        let p = self.same_field(divisor);
+        let divisor_degree = divisor.degree().expect("attempt to divide by zero");
+        let inverse = mod_inv(divisor.coefficients[divisor_degree], p).unwrap();
+
+        let mut remainder = self.coefficients.clone();
+        let Some(quotient_len) = (remainder.len() + 1).checked_sub(divisor.coefficients.len())
+        else {
+            return (Polynomial::zero(p), self.clone());
+        };
+        let mut quotient = vec![0; quotient_len];
+
+        for shift in (0..quotient_len).rev() {
+            let factor = mod_mul(remainder[shift + divisor_degree], inverse, p);
+            quotient[shift] = factor;
+            for (r, &d) in remainder[shift..].iter_mut().zip(&divisor.coefficients) {
+                *r = sub_mod(*r, mod_mul(factor, d, p), p);
+            }
+        }
+
+        remainder.truncate(divisor_degree);
+        (
+            Polynomial::from_reduced(quotient, p),
+            Polynomial::from_reduced(remainder, p),
+        )
+    }
+
+    /// Remainder of the division by `divisor`.
+    pub fn rem(&self, divisor: &Polynomial) -> Polynomial {
+        self.div_rem(divisor).1
+    }
+
+    /// Monic greatest common divisor - zero only if both are zero.
+    pub fn gcd(&self, other: &Polynomial) -> Polynomial {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let r = a.rem(&b);
+            (a, b) = (b, r);
+        }
+        a.monic()
+    }
+
+    /// `self^exp mod modulus` by square-and-multiply.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is zero.
+    pub fn pow_mod(&self, mut exp: u64, modulus: &Polynomial) -> Polynomial {
+        let mut base = self.rem(modulus);
+        let mut result = Polynomial::new(vec![1], self.p).rem(modulus);
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = (&result * &base).rem(modulus);
+            }
+            base = (&base * &base).rem(modulus);
+            exp >>= 1;
+        }
+        result
+    }
+
+    /// Rabin's test: a polynomial `f` of degree `n` over `GF(p)` is irreducible iff
+    /// `x^(p^n) = x (mod f)` and `gcd(f, x^(p^(n/q)) - x) = 1` for every prime `q | n`.
+    ///
+    /// Constants - units and zero - are not irreducible.
+    pub fn is_irreducible(&self) -> bool {
+        let n = match self.degree() {
+            None | Some(0) => return false,
+            Some(1) => return true,
+            Some(n) => n,
+        };
+        let f = self.monic();
+        let x = Polynomial::x(self.p);
+
+        // frobenius[k] = x^(p^k) mod f, each one the p-th power of the previous
+        let mut frobenius = vec![x.rem(&f)];
+        for k in 1..=n {
+            let next = frobenius[k - 1].pow_mod(self.p, &f);
+            frobenius.push(next);
+        }
+
+        if frobenius[n] != x.rem(&f) {
+            return false;
+        }
+        factorize(n as u64).into_iter().all(|(q, _)| {
+            let h = &frobenius[n / q as usize] - &x;
+            f.gcd(&h).degree() == Some(0)
+        })
+    }
+
+    fn same_field(&self, other: &Polynomial) -> u64 {
+        assert_eq!(self.p, other.p, "polynomials over different fields");
+        self.p
+    }
+}
+
+fn add_mod(a: u64, b: u64, p: u64) -> u64 {
+    let (sum, overflow) = a.overflowing_add(b);
+    if overflow || sum >= p {
+        sum.wrapping_sub(p)
+    } else {
+        sum
+    }
+}
+
+fn sub_mod(a: u64, b: u64, p: u64) -> u64 {
+    if a >= b {
+        a - b
+    } else {
+        a.wrapping_sub(b).wrapping_add(p)
+    }
+}
+
+impl Add for &Polynomial {
+    type Output = Polynomial;
+
+    fn add(self, other: &Polynomial) -> Polynomial {
+        let p = self.same_field(other);
+        let len = self.coefficients.len().max(other.coefficients.len());
+        let coefficient =
+            |poly: &Polynomial, i: usize| poly.coefficients.get(i).copied().unwrap_or(0);
+        let coefficients = (0..len)
+            .map(|i| add_mod(coefficient(self, i), coefficient(other, i), p))
+            .collect();
+        Polynomial::from_reduced(coefficients, p)
+    }
+}
+
+impl Sub for &Polynomial {
+    type Output = Polynomial;
+
+    fn sub(self, other: &Polynomial) -> Polynomial {
+        self + &-other
+    }
+}
+
+impl Neg for &Polynomial {
+    type Output = Polynomial;
+
+    fn neg(self) -> Polynomial {
+        let coefficients = self
+            .coefficients
+            .iter()
+            .map(|&c| sub_mod(0, c, self.p))
+            .collect();
+        Polynomial::from_reduced(coefficients, self.p)
+    }
+}
+
+impl Mul for &Polynomial {
+    type Output = Polynomial;
+
+    fn mul(self, other: &Polynomial) -> Polynomial {
+        let p = self.same_field(other);
+        if self.is_zero() || other.is_zero() {
+            return Polynomial::zero(p);
+        }
+
+        let mut coefficients = vec![0; self.coefficients.len() + other.coefficients.len() - 1];
+        for (i, &a) in self.coefficients.iter().enumerate() {
+            for (c, &b) in coefficients[i..].iter_mut().zip(&other.coefficients) {
+                *c = add_mod(*c, mod_mul(a, b, p), p);
+            }
+        }
+        Polynomial::from_reduced(coefficients, p)
+    }
+}
+
+/// Formats as `x^3 + 2x + 1`, highest degree first; the field is not shown.
+impl fmt::Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+
+        let mut first = true;
+        for (degree, &c) in self.coefficients.iter().enumerate().rev() {
+            if c == 0 {
+                continue;
+            }
+            if !first {
+                write!(f, " + ")?;
+            }
+            first = false;
+
+            match (c, degree) {
+                (c, 0) => write!(f, "{}", c)?,
+                (1, 1) => write!(f, "x")?,
+                (1, d) => write!(f, "x^{}", d)?,
+                (c, 1) => write!(f, "{}x", c)?,
+                (c, d) => write!(f, "{}x^{}", c, d)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// All monic irreducible polynomials of `degree` over `GF(p)`, in lexicographic
+/// order of their coefficients from `x^(degree - 1)` down to the constant.
+///
+/// There are `(1 / n) * sum(mobius(d) * p^(n / d))` of them for `n = degree`,
+/// see [`count_irreducible`].
+///
+/// # Panics
+///
+/// Panics if `p` is not prime or `degree == 0`.
+pub fn irreducible_polynomials(degree: usize, p: u64) -> impl Iterator<Item = Polynomial> {
+    assert!(is_prime(p), "polynomial coefficients need a prime modulus");
+    assert!(degree > 0, "constants are never irreducible");
+
+    // the lower coefficients count up like the digits of a base-p number
+    let mut lower = Some(vec![0u64; degree]);
+    core::iter::from_fn(move || {
+        let coefficients = lower.as_mut()?;
+        let mut candidate = coefficients.clone();
+        candidate.push(1);
+
+        match coefficients.iter().position(|&c| c + 1 < p) {
+            Some(i) => {
+                coefficients[i] += 1;
+                coefficients[..i].fill(0);
+            }
+            None => lower = None,
+        }
+        Some(Polynomial::from_reduced(candidate, p))
+    })
+    .filter(Polynomial::is_irreducible)
+}
+
+/// Number of monic irreducible polynomials of `degree` over `GF(p)` (Gauss's formula),
+/// `None` if it does not fit in `u64`.
+pub fn count_irreducible(degree: u32, p: u64) -> Option<u64> {
+    if degree == 0 {
+        return Some(0);
+    }
+
+    let mut sum: i128 = 0;
+    for d in (1..=degree).filter(|&d| degree.is_multiple_of(d)) {
+        let sign = mobius(d as u64) as i128;
+        if sign != 0 {
+            sum += sign * p.checked_pow(degree / d)? as i128;
+        }
+    }
+    u64::try_from(sum / degree as i128).ok()
+}
@@ -0,0 +1,34 @@
+use core::fmt::{Debug, Display};
+
+/// Unsigned integer types accepted by the generic functions in [`crate::math_utils`].
+///
+/// Every value is widened to `u128` for the actual test - values that fit in `u64`
+/// take the `u64` fast path, larger ones are handled with Montgomery arithmetic.
+pub trait PrimeInt: Copy + Ord + Debug + Display + Send + Sync + 'static {
+    const MAX: Self;
+
+    fn to_u128(self) -> u128;
+
+    /// Narrows `value` back to `Self`, `None` if it does not fit.
+    fn from_u128(value: u128) -> Option<Self>;
+}
+
+macro_rules! impl_prime_int {
+    ($($t:ty),*) => {
+        $(
+            impl PrimeInt for $t {
+                const MAX: Self = <$t>::MAX;
+
+                fn to_u128(self) -> u128 {
+                    self as u128
+                }
+
+                fn from_u128(value: u128) -> Option<Self> {
+                    <$t>::try_from(value).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_prime_int!(u8, u16, u32, u64, u128, usize);
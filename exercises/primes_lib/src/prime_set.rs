@@ -0,0 +1,306 @@
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::fs::File;
+#[cfg(feature = "std")]
+use std::io::{self, BufReader, BufWriter, Read, Write};
+#[cfg(feature = "std")]
+use std::path::Path;
+
+use crate::counting::{nth_prime, prime_pi};
+use crate::math_utils::{is_prime, next_prime_after};
+use crate::sieve::SegmentedSieve;
+
+#[cfg(feature = "std")]
+const MAGIC: &[u8; 8] = b"PRIMESET";
+#[cfg(feature = "std")]
+const FORMAT_VERSION: u8 = 1;
+
+/// Precomputed table of the primes up to a limit.
+///
+/// One bit per odd number (bit `i` of the table stands for `2i + 1`) plus a running
+/// count of primes before every 64-bit word, so `contains` and `rank` are O(1) and
+/// `select` is a binary search. Queries above the limit fall back to [`is_prime`],
+/// [`prime_pi`] and [`nth_prime`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrimeSet {
+    limit: u64,
+    words: Vec<u64>,
+    /// `word_ranks[w]` - number of odd primes in `words[..w]`, one extra entry for the total
+    word_ranks: Vec<u64>,
+}
+
+impl PrimeSet {
+    /// Sieves every prime `<= limit`.
+    pub fn new(limit: u64) -> PrimeSet {
+        let mut words = vec![0u64; word_count(limit)];
+
+        let mut sieve = SegmentedSieve::new(3..limit.saturating_add(1));
+        let mut segment = Vec::new();
+        while sieve.next_segment(&mut segment) {
+            for &p in &segment {
+                let i = (p / 2) as usize;
+                words[i / 64] |= 1 << (i % 64);
+            }
+            segment.clear();
+        }
+
+        PrimeSet::from_words(limit, words)
+    }
+
+    fn from_words(limit: u64, words: Vec<u64>) -> PrimeSet {
+        let mut word_ranks = Vec::with_capacity(words.len() + 1);
+        let mut total = 0;
+        word_ranks.push(0);
+        for word in &words {
+            total += word.count_ones() as u64;
+            word_ranks.push(total);
+        }
+
+        PrimeSet {
+            limit,
+            words,
+            word_ranks,
+        }
+    }
+
+    /// Largest value answered from the table.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Number of primes in the table.
+    pub fn len(&self) -> u64 {
+        self.word_ranks.last().unwrap() + (self.limit >= 2) as u64
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn contains(&self, n: u64) -> bool {
+        if n > self.limit {
+            return is_prime(n);
+        }
+        if n.is_multiple_of(2) {
+            return n == 2;
+        }
+        self.bit((n / 2) as usize)
+    }
+
+    /// Smallest prime `> n`, `None` if it does not fit in `u64`.
+    pub fn next_prime_after(&self, n: u64) -> Option<u64> {
+        if n < 2 {
+            return Some(2);
+        }
+
+        if n < self.limit {
+            // bit of the first odd number above n
+            let start = n.div_ceil(2) as usize;
+            if let Some(i) = self.next_set_bit(start) {
+                return Some(2 * i as u64 + 1);
+            }
+        }
+
+        next_prime_after(n.max(self.limit))
+    }
+
+    /// Largest prime `< n`, `None` for `n <= 2`.
+    pub fn prev_prime_before(&self, n: u64) -> Option<u64> {
+        if n <= 2 {
+            return None;
+        }
+        if n == 3 {
+            return Some(2);
+        }
+
+        // candidates above the table are tested one by one
+        let mut candidate = n - 1;
+        while candidate > self.limit {
+            if is_prime(candidate) {
+                return Some(candidate);
+            }
+            candidate -= 1;
+        }
+
+        // candidate >= 3 here, look for the last odd prime <= candidate
+        let end = ((candidate - 1) / 2) as usize;
+        match self.prev_set_bit(end) {
+            Some(i) => Some(2 * i as u64 + 1),
+            None => Some(2),
+        }
+    }
+
+    /// Number of primes `<= n` (pi(n)).
+    ///
+    /// # Panics
+    ///
+    /// Panics above the table if `n > PRIME_PI_LIMIT`, like [`prime_pi`].
+    pub fn rank(&self, n: u64) -> u64 {
+        if n > self.limit {
+            return prime_pi(n);
+        }
+        if n < 2 {
+            return 0;
+        }
+
+        // odd numbers 1, 3, ..., n (or n - 1) are bits 0..=last
+        let last = ((n - 1) / 2) as usize;
+        let (word, bit) = (last / 64, last % 64);
+        let mask = u64::MAX >> (63 - bit);
+
+        1 + self.word_ranks[word] + (self.words[word] & mask).count_ones() as u64
+    }
+
+    /// The `k`-th prime, counting from `select(1) == 2`; `None` for `k == 0`.
+    ///
+    /// # Panics
+    ///
+    /// Panics above the table if `k > NTH_PRIME_LIMIT`, like [`nth_prime`].
+    pub fn select(&self, k: u64) -> Option<u64> {
+        if k == 0 {
+            return None;
+        }
+        if k > self.len() {
+            return Some(nth_prime(k));
+        }
+        if k == 1 {
+            return Some(2);
+        }
+
+        // index among the odd primes
+        let j = k - 2;
+        let word = self.word_ranks.partition_point(|&rank| rank <= j) - 1;
+        let mut bits = self.words[word];
+        for _ in 0..(j - self.word_ranks[word]) {
+            bits &= bits - 1;
+        }
+
+        let i = word * 64 + bits.trailing_zeros() as usize;
+        Some(2 * i as u64 + 1)
+    }
+
+    #[cfg(feature = "std")]
+    /// Writes the table in a compact binary format: magic, version, limit and the
+    /// bit words, all little-endian.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[FORMAT_VERSION])?;
+        writer.write_all(&self.limit.to_le_bytes())?;
+        for word in &self.words {
+            writer.write_all(&word.to_le_bytes())?;
+        }
+        writer.flush()
+    }
+
+    #[cfg(feature = "std")]
+    /// Reads a table written by [`PrimeSet::write_to`].
+    ///
+    /// Fails with [`io::ErrorKind::InvalidData`] if the table is too short or too
+    /// long for its limit, has bits above the limit, or marks 1 as prime.
+    pub fn read_from<R: Read>(mut reader: R) -> io::Result<PrimeSet> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("not a PrimeSet file"));
+        }
+
+        let mut version = [0u8; 1];
+        reader.read_exact(&mut version)?;
+        if version[0] != FORMAT_VERSION {
+            return Err(invalid_data("unsupported PrimeSet format version"));
+        }
+
+        let mut buffer = [0u8; 8];
+        reader.read_exact(&mut buffer)?;
+        let limit = u64::from_le_bytes(buffer);
+
+        // the limit is not trusted with an allocation: the table only grows as
+        // its words are actually read
+        let mut words = Vec::new();
+        for _ in 0..word_count(limit) {
+            reader.read_exact(&mut buffer).map_err(|e| match e.kind() {
+                io::ErrorKind::UnexpectedEof => {
+                    invalid_data("PrimeSet table is shorter than its limit")
+                }
+                _ => e,
+            })?;
+            words.push(u64::from_le_bytes(buffer));
+        }
+
+        if reader.read(&mut buffer)? != 0 {
+            return Err(invalid_data("trailing data after PrimeSet table"));
+        }
+        if !tail_is_clear(limit, &words) {
+            return Err(invalid_data("PrimeSet table has bits above its limit"));
+        }
+        // bit 0 stands for 1, which is not prime
+        if words[0] & 1 != 0 {
+            return Err(invalid_data("PrimeSet table marks 1 as prime"));
+        }
+
+        Ok(PrimeSet::from_words(limit, words))
+    }
+
+    #[cfg(feature = "std")]
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write_to(BufWriter::new(File::create(path)?))
+    }
+
+    #[cfg(feature = "std")]
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<PrimeSet> {
+        PrimeSet::read_from(BufReader::new(File::open(path)?))
+    }
+
+    fn bit(&self, i: usize) -> bool {
+        self.words[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    /// First set bit with index `>= start`.
+    fn next_set_bit(&self, start: usize) -> Option<usize> {
+        let mut word = start / 64;
+        if word >= self.words.len() {
+            return None;
+        }
+
+        let mut bits = self.words[word] & (u64::MAX << (start % 64));
+        loop {
+            if bits != 0 {
+                return Some(word * 64 + bits.trailing_zeros() as usize);
+            }
+            word += 1;
+            bits = *self.words.get(word)?;
+        }
+    }
+
+    /// Last set bit with index `<= end`.
+    fn prev_set_bit(&self, end: usize) -> Option<usize> {
+        let mut word = end / 64;
+        let mut bits = self.words[word] & (u64::MAX >> (63 - end % 64));
+        loop {
+            if bits != 0 {
+                return Some(word * 64 + 63 - bits.leading_zeros() as usize);
+            }
+            word = word.checked_sub(1)?;
+            bits = self.words[word];
+        }
+    }
+}
+
+/// Words needed for the odd numbers `1..=limit`.
+fn word_count(limit: u64) -> usize {
+    let bits = limit.div_ceil(2) as usize;
+    bits.div_ceil(64).max(1)
+}
+
+#[cfg(feature = "std")]
+fn tail_is_clear(limit: u64, words: &[u64]) -> bool {
+    let bits = limit.div_ceil(2) as usize;
+    let used_in_last = bits - (words.len() - 1) * 64;
+    used_in_last >= 64 || words[words.len() - 1] >> used_in_last == 0
+}
+
+#[cfg(feature = "std")]
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,49 @@
+/// Source of uniformly distributed random `u64` values.
+///
+/// Implement it on top of an OS or cryptographic generator when the numbers must
+/// be unpredictable - [`Xoshiro256`] is only meant for reproducible runs.
+pub trait RandomSource {
+    fn next_u64(&mut self) -> u64;
+}
+
+/// xoshiro256** generator - fast and reproducible from a seed, not cryptographically secure.
+#[derive(Debug, Clone)]
+pub struct Xoshiro256 {
+    state: [u64; 4],
+}
+
+impl Xoshiro256 {
+    /// Expands `seed` into the full state with SplitMix64, as recommended by the
+    /// xoshiro authors - any seed, including 0, gives a usable state.
+    pub fn seed_from_u64(seed: u64) -> Xoshiro256 {
+        let mut x = seed;
+        let mut split_mix = || {
+            x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = x;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        };
+
+        Xoshiro256 {
+            state: [split_mix(), split_mix(), split_mix(), split_mix()],
+        }
+    }
+}
+
+impl RandomSource for Xoshiro256 {
+    fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+
+        result
+    }
+}
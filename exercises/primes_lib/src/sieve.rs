@@ -0,0 +1,344 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::math_utils::is_prime_fast;
+use crate::small_primes::{primes_below, SMALL_PRIME_TABLE_LIMIT};
+
+/// Number of odd candidates sieved at once - 32 KiB of flags fits in L1 data cache.
+pub const SEGMENT_SIZE: usize = 32 * 1024;
+
+/// Number of 64-bit words in the window of a [`StackSieve`] - 512 bytes, 4096 odd candidates.
+pub const STACK_WINDOW_WORDS: usize = 64;
+
+/// Largest base prime kept in memory. Segments above `MAX_BASE_PRIME^2` are only
+/// pre-sieved, and the survivors are confirmed with Miller-Rabin.
+pub const MAX_BASE_PRIME: u64 = 1 << 21;
+
+/// Classic Sieve of Eratosthenes - returns all primes `<= limit`.
+pub fn simple_sieve(limit: u32) -> Vec<u32> {
+    let limit = limit as usize;
+    if limit < 2 {
+        return Vec::new();
+    }
+
+    let mut is_composite = vec![false; limit + 1];
+    let mut primes = Vec::new();
+
+    for i in 2..=limit {
+        if is_composite[i] {
+            continue;
+        }
+        primes.push(i as u32);
+        for multiple in (i * i..=limit).step_by(i) {
+            is_composite[multiple] = true;
+        }
+    }
+
+    primes
+}
+
+/// Index, in a segment of odd numbers starting at the odd `seg_low`, of the first
+/// odd multiple of `p` that is `>= max(p^2, seg_low)`.
+///
+/// Returns `None` if that multiple does not fit in a `u64`.
+fn first_odd_multiple(p: u64, seg_low: u64) -> Option<usize> {
+    let square = p * p;
+    let mut start = if square >= seg_low {
+        square
+    } else {
+        seg_low.checked_next_multiple_of(p)?
+    };
+    if start.is_multiple_of(2) {
+        start = start.checked_add(p)?;
+    }
+    Some(((start - seg_low) / 2) as usize)
+}
+
+/// Segmented Sieve of Eratosthenes over a half-open range of `u64`.
+///
+/// Only one segment of [`SEGMENT_SIZE`] odd candidates is kept in memory, and base
+/// primes are extended lazily, so the sieve can start anywhere (e.g. at `10^12`) and
+/// run without a known upper bound (`2..u64::MAX`).
+pub struct SegmentedSieve {
+    base_primes: Vec<u32>,
+    base_limit: u64,
+    low: u64,
+    high: u64,
+    flags: Vec<bool>,
+}
+
+impl SegmentedSieve {
+    pub fn new(range: Range<u64>) -> SegmentedSieve {
+        SegmentedSieve {
+            base_primes: Vec::new(),
+            base_limit: 1,
+            low: range.start,
+            high: range.end.max(range.start),
+            flags: vec![false; SEGMENT_SIZE],
+        }
+    }
+
+    /// Start of the part of the range that has not been sieved yet.
+    pub fn position(&self) -> u64 {
+        self.low
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.low >= self.high
+    }
+
+    /// Sieves the next segment and appends its primes to `out` in ascending order.
+    ///
+    /// Returns `false` once the whole range has been processed.
+    pub fn next_segment<E: Extend<u64>>(&mut self, out: &mut E) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+
+        if self.low <= 2 && self.high > 2 {
+            out.extend(Some(2));
+        }
+
+        // the segment holds odd numbers only: flags[i] <=> seg_low + 2 * i
+        let seg_low = self.low | 1;
+        let span = 2 * SEGMENT_SIZE as u64;
+        let seg_high = seg_low.saturating_add(span).min(self.high);
+
+        if seg_low < seg_high {
+            let len = (seg_high - seg_low).div_ceil(2) as usize;
+            let sqrt_high = (seg_high - 1).isqrt();
+            self.extend_base_primes(sqrt_high.min(MAX_BASE_PRIME));
+            self.sieve_segment(seg_low, len);
+
+            let fully_sieved = sqrt_high <= self.base_limit;
+            let primes = self.flags[..len]
+                .iter()
+                .enumerate()
+                .filter(|(_, &is_prime)| is_prime)
+                .map(|(i, _)| seg_low + 2 * i as u64)
+                .filter(|&n| fully_sieved || is_prime_fast(n));
+            out.extend(primes);
+        }
+
+        self.low = seg_high;
+        true
+    }
+
+    fn sieve_segment(&mut self, seg_low: u64, len: usize) {
+        let flags = &mut self.flags[..len];
+        flags.fill(true);
+        if seg_low == 1 {
+            flags[0] = false;
+        }
+
+        let seg_last = seg_low + 2 * (len as u64 - 1);
+
+        // base_primes[0] == 2 - even numbers are not stored in the segment
+        for &p in self.base_primes.iter().skip(1) {
+            let p = p as u64;
+            if p * p > seg_last {
+                break;
+            }
+
+            let Some(first) = first_odd_multiple(p, seg_low) else {
+                continue;
+            };
+            for i in (first..len).step_by(p as usize) {
+                flags[i] = false;
+            }
+        }
+    }
+
+    /// Makes sure `base_primes` contains every prime `<= limit`.
+    fn extend_base_primes(&mut self, limit: u64) {
+        if limit <= self.base_limit {
+            return;
+        }
+
+        // grow geometrically so that an open-ended sieve recomputes base primes rarely
+        let new_limit = limit
+            .max(self.base_limit.saturating_mul(2))
+            .min(MAX_BASE_PRIME);
+        self.base_primes = simple_sieve(new_limit as u32);
+        self.base_limit = new_limit;
+    }
+}
+
+/// Allocation-free prime iterator over a half-open range of `u64`.
+///
+/// Works like [`SegmentedSieve`], but the window is a fixed bit array inside the
+/// struct and the base primes come from the compile-time table in
+/// [`small_primes`](crate::small_primes), so it never touches the heap. Windows
+/// below 2^32 are sieved completely; higher up they are only pre-sieved by the
+/// primes below 2^16 and the survivors are confirmed with Miller-Rabin.
+pub struct StackSieve {
+    low: u64,
+    high: u64,
+    /// bit `i` set <=> `window_low + 2 * i` is still a candidate
+    window: [u64; STACK_WINDOW_WORDS],
+    window_low: u64,
+    next_bit: usize,
+    fully_sieved: bool,
+    two_pending: bool,
+}
+
+impl StackSieve {
+    pub fn new(range: Range<u64>) -> StackSieve {
+        StackSieve {
+            low: range.start,
+            high: range.end.max(range.start),
+            window: [0; STACK_WINDOW_WORDS],
+            window_low: 1,
+            next_bit: 0,
+            fully_sieved: true,
+            two_pending: range.start <= 2 && range.end > 2,
+        }
+    }
+
+    /// Sieves the next window; `false` once the whole range has been processed.
+    fn sieve_window(&mut self) -> bool {
+        if self.low >= self.high {
+            return false;
+        }
+
+        let seg_low = self.low | 1;
+        let span = 2 * 64 * STACK_WINDOW_WORDS as u64;
+        let seg_high = seg_low.saturating_add(span).min(self.high);
+        self.low = seg_high;
+        self.window_low = seg_low;
+        self.next_bit = 0;
+        self.window.fill(0);
+        if seg_low >= seg_high {
+            return true;
+        }
+
+        let len = (seg_high - seg_low).div_ceil(2) as usize;
+        for (i, word) in self.window.iter_mut().enumerate() {
+            let bits = len.saturating_sub(64 * i).min(64);
+            *word = u64::MAX.checked_shr(64 - bits as u32).unwrap_or(0);
+        }
+        if seg_low == 1 {
+            self.window[0] &= !1;
+        }
+
+        let seg_last = seg_low + 2 * (len as u64 - 1);
+        for p in primes_below(SMALL_PRIME_TABLE_LIMIT).skip(1) {
+            let p = p as u64;
+            if p * p > seg_last {
+                break;
+            }
+
+            let Some(first) = first_odd_multiple(p, seg_low) else {
+                continue;
+            };
+            for i in (first..len).step_by(p as usize) {
+                self.window[i / 64] &= !(1 << (i % 64));
+            }
+        }
+
+        self.fully_sieved = seg_last.isqrt() < SMALL_PRIME_TABLE_LIMIT as u64;
+        true
+    }
+}
+
+impl Iterator for StackSieve {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.two_pending {
+            self.two_pending = false;
+            return Some(2);
+        }
+
+        loop {
+            while self.next_bit < 64 * STACK_WINDOW_WORDS {
+                let word = self.next_bit / 64;
+                let bits = self.window[word] & (u64::MAX << (self.next_bit % 64));
+                if bits == 0 {
+                    self.next_bit = 64 * (word + 1);
+                    continue;
+                }
+
+                let bit = 64 * word + bits.trailing_zeros() as usize;
+                self.next_bit = bit + 1;
+                let n = self.window_low + 2 * bit as u64;
+                if self.fully_sieved || is_prime_fast(n) {
+                    return Some(n);
+                }
+            }
+
+            if !self.sieve_window() {
+                return None;
+            }
+        }
+    }
+}
+
+impl FusedIterator for StackSieve {}
+
+/// Collects all primes in `range` with the segmented sieve.
+pub fn sieve_range(range: Range<u64>) -> Vec<u64> {
+    let mut sieve = SegmentedSieve::new(range);
+    let mut primes = Vec::new();
+    while sieve.next_segment(&mut primes) {}
+    primes
+}
+
+/// Collects the first `count` primes with an open-ended segmented sieve.
+pub fn sieve_first(count: usize) -> Vec<u64> {
+    let mut sieve = SegmentedSieve::new(2..u64::MAX);
+    let mut primes = Vec::with_capacity(count);
+    while primes.len() < count && sieve.next_segment(&mut primes) {}
+    primes.truncate(count);
+    primes
+}
+
+/// Linear (Euler's) sieve - crosses out every composite exactly once, by its
+/// smallest prime factor, and keeps that factor for each number.
+pub struct LinearSieve {
+    /// `smallest_factor[n]` for `0..=limit`, with `0` for `0` and `1`
+    pub smallest_factor: Vec<u32>,
+    pub primes: Vec<u32>,
+}
+
+impl LinearSieve {
+    /// # Panics
+    ///
+    /// Panics if `limit` does not fit in `u32`.
+    pub fn new(limit: usize) -> LinearSieve {
+        assert!(
+            limit <= u32::MAX as usize,
+            "linear sieve limit must fit in u32"
+        );
+
+        let mut smallest_factor = vec![0u32; limit + 1];
+        let mut primes = Vec::new();
+
+        for i in 2..=limit {
+            if smallest_factor[i] == 0 {
+                smallest_factor[i] = i as u32;
+                primes.push(i as u32);
+            }
+
+            let spf = smallest_factor[i];
+            for &p in &primes {
+                let multiple = i * p as usize;
+                if p > spf || multiple > limit {
+                    break;
+                }
+                smallest_factor[multiple] = p;
+            }
+        }
+
+        LinearSieve {
+            smallest_factor,
+            primes,
+        }
+    }
+
+    pub fn limit(&self) -> usize {
+        self.smallest_factor.len() - 1
+    }
+}
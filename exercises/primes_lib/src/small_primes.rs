@@ -0,0 +1,119 @@
+/// Values below this bound are answered by [`is_small_prime`] with a single lookup.
+pub const SMALL_PRIME_TABLE_LIMIT: u32 = 1 << 16;
+
+/// Number of primes below 2^10.
+const SMALL_PRIME_COUNT: usize = 172;
+
+/// The primes below 2^10 - enough to trial-divide every `n < 2^20`.
+pub const SMALL_PRIMES: [u16; SMALL_PRIME_COUNT] = build_small_primes();
+
+/// Product of the primes the wheel skips: 2 * 3 * 5 * 7.
+pub const WHEEL_MODULUS: u64 = 210;
+
+/// Differences between consecutive integers coprime to [`WHEEL_MODULUS`], starting at 1.
+/// Adding them in turn to `k * 210 + 1` visits only numbers without a factor below 11.
+pub const WHEEL_GAPS: [u8; 48] = build_wheel_gaps();
+
+/// Bit `i` is set if `2i + 1` is prime, for every odd number below 2^16.
+const PRIME_TABLE: [u64; 512] = build_prime_table();
+
+/// Table lookup for the whole `u16` range.
+pub fn is_small_prime(n: u16) -> bool {
+    if n.is_multiple_of(2) {
+        return n == 2;
+    }
+    let i = (n / 2) as usize;
+    PRIME_TABLE[i / 64] >> (i % 64) & 1 == 1
+}
+
+/// The primes below `limit` in ascending order, read from the same table as
+/// [`is_small_prime`] - nothing is sieved or allocated at run time.
+///
+/// # Panics
+///
+/// Panics if `limit > SMALL_PRIME_TABLE_LIMIT`.
+pub fn primes_below(limit: u32) -> impl Iterator<Item = u32> {
+    assert!(
+        limit <= SMALL_PRIME_TABLE_LIMIT,
+        "the prime table ends at 2^16"
+    );
+
+    let odd_primes = PRIME_TABLE.iter().enumerate().flat_map(|(index, &word)| {
+        let mut bits = word;
+        core::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let bit = bits.trailing_zeros() as usize;
+            bits &= bits - 1;
+            Some(2 * (index * 64 + bit) as u32 + 1)
+        })
+    });
+    core::iter::once(2)
+        .chain(odd_primes)
+        .take_while(move |&p| p < limit)
+}
+
+const fn build_prime_table() -> [u64; 512] {
+    // odd-only sieve of Eratosthenes, run by the compiler
+    let mut composite = [0u64; 512];
+    composite[0] = 1;
+
+    let mut i = 1;
+    while i < 128 {
+        if composite[i / 64] >> (i % 64) & 1 == 0 {
+            let p = 2 * i + 1;
+            let mut j = p * p / 2;
+            while j < 32_768 {
+                composite[j / 64] |= 1 << (j % 64);
+                j += p;
+            }
+        }
+        i += 1;
+    }
+
+    let mut table = [0u64; 512];
+    let mut k = 0;
+    while k < 512 {
+        table[k] = !composite[k];
+        k += 1;
+    }
+    table
+}
+
+const fn build_small_primes() -> [u16; SMALL_PRIME_COUNT] {
+    let table = build_prime_table();
+    let mut primes = [2u16; SMALL_PRIME_COUNT];
+
+    let mut count = 1;
+    let mut i = 1;
+    while count < SMALL_PRIME_COUNT {
+        if table[i / 64] >> (i % 64) & 1 == 1 {
+            primes[count] = (2 * i + 1) as u16;
+            count += 1;
+        }
+        i += 1;
+    }
+    primes
+}
+
+const fn build_wheel_gaps() -> [u8; 48] {
+    let mut gaps = [0u8; 48];
+    let mut count = 0;
+    let mut previous = 1;
+
+    let mut n = 2;
+    while n <= WHEEL_MODULUS + 1 {
+        if !n.is_multiple_of(2)
+            && !n.is_multiple_of(3)
+            && !n.is_multiple_of(5)
+            && !n.is_multiple_of(7)
+        {
+            gaps[count] = (n - previous) as u8;
+            count += 1;
+            previous = n;
+        }
+        n += 1;
+    }
+    gaps
+}
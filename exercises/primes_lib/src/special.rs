@@ -0,0 +1,180 @@
+//! Deterministic tests for primes of special forms.
+//!
+//! Each one needs about as many modular squarings as the number has bits, where a
+//! general test would need trial division or many probable-prime rounds - the
+//! reason record primes all have one of these shapes.
+
+use crate::big_primes::jacobi_big;
+use crate::bigint::BigUint;
+use crate::math_utils::{is_prime, is_prime_fast};
+
+/// Lucas-Lehmer test - `true` if the Mersenne number `2^p - 1` is prime.
+///
+/// `s_0 = 4, s_i = s_(i-1)^2 - 2`; for an odd prime `p`, `2^p - 1` is prime iff
+/// `s_(p-2) = 0 (mod 2^p - 1)`. A composite `p` always gives a composite number.
+pub fn lucas_lehmer(p: u64) -> bool {
+    if p == 2 {
+        return true;
+    }
+    if !is_prime(p) {
+        return false;
+    }
+
+    let m = mersenne(p);
+    let m_minus_two = &m - 2;
+    let mut s = BigUint::from(4u64);
+    for _ in 0..p - 2 {
+        // s^2 - 2 = s^2 + (m - 2) (mod m), which never goes below zero
+        s = reduce_mersenne(s.square() + &m_minus_two, p, &m);
+    }
+    s.is_zero()
+}
+
+/// Pépin's test - `true` if the Fermat number `F_m = 2^(2^m) + 1` is prime.
+///
+/// `F_m` with `m >= 1` is prime iff `3^((F_m - 1) / 2) = -1 (mod F_m)`, which takes
+/// `2^m - 1` squarings. Only `F_0` to `F_4` are known to be prime.
+///
+/// # Panics
+///
+/// Panics if `m >= 64`.
+pub fn pepin(m: u32) -> bool {
+    assert!(m < 64, "F_{} has too many digits to test", m);
+    if m == 0 {
+        return true;
+    }
+
+    let n = 1u64 << m;
+    let f = (BigUint::one() << n) + 1;
+    // (F_m - 1) / 2 = 2^(2^m - 1)
+    let mut x = BigUint::from(3u64);
+    for _ in 0..n - 1 {
+        x = reduce_fermat(x.square(), n, &f);
+    }
+    x == f - 1
+}
+
+/// Proth's theorem - `true` if `k * 2^n + 1` is prime, for odd `k < 2^n`.
+///
+/// `N` is prime iff `a^((N - 1) / 2) = -1 (mod N)` for some `a`. Choosing `a` with
+/// Jacobi symbol `(a / N) = -1` makes the test deterministic: a prime `N` must
+/// pass with that `a`, so a single exponentiation decides.
+///
+/// # Panics
+///
+/// Panics if `k` is even or `k >= 2^n`.
+pub fn proth(k: u64, n: u64) -> bool {
+    check_form(k, n);
+
+    let number = (BigUint::from(k) << n) + 1;
+    if let Some(number) = number.to_u64() {
+        return is_prime_fast(number);
+    }
+    // a square has no non-residues
+    let root = number.isqrt();
+    if root.square() == number {
+        return false;
+    }
+
+    let n_minus_one = &number - 1;
+    let exponent = &n_minus_one >> 1;
+    for a in (3u64..).filter(|&a| is_prime(a)) {
+        match jacobi_big(a as i64, &number) {
+            -1 => return BigUint::from(a).pow_mod(&exponent, &number) == n_minus_one,
+            0 => return false,
+            _ => continue,
+        }
+    }
+    unreachable!()
+}
+
+/// Lucas-Lehmer-Riesel test - `true` if `k * 2^n - 1` is prime, for odd `k < 2^n`.
+///
+/// Uses Rödseth's starting value: for `P` with `((P - 2) / N) = 1` and
+/// `((P + 2) / N) = -1`, `u_0 = V_k(P, 1) mod N` and `u_i = u_(i-1)^2 - 2`,
+/// `N` is prime iff `u_(n-2) = 0 (mod N)`. With `k = 1` this is [`lucas_lehmer`].
+///
+/// # Panics
+///
+/// Panics if `k` is even or `k >= 2^n`.
+pub fn llr(k: u64, n: u64) -> bool {
+    check_form(k, n);
+
+    let number = (BigUint::from(k) << n) - 1;
+    if let Some(number) = number.to_u64() {
+        return is_prime_fast(number);
+    }
+
+    // N = 7 (mod 8) is never a square, so a suitable P exists
+    let mut p = 3;
+    loop {
+        match (jacobi_big(p - 2, &number), jacobi_big(p + 2, &number)) {
+            (1, -1) => break,
+            (0, _) | (_, 0) => return false,
+            _ => p += 1,
+        }
+    }
+
+    let number_minus_two = &number - 2;
+    let mut u = lucas_v(k, p as u64, &number);
+    for _ in 0..n - 2 {
+        u = (u.square() + &number_minus_two) % &number;
+    }
+    u.is_zero()
+}
+
+fn check_form(k: u64, n: u64) {
+    assert!(k % 2 == 1, "k must be odd");
+    assert!(
+        n >= 64 || k < 1 << n,
+        "k must be smaller than 2^n for the test to apply"
+    );
+}
+
+/// `2^p - 1`.
+pub fn mersenne(p: u64) -> BigUint {
+    (BigUint::one() << p) - 1
+}
+
+/// `x mod m` for `m = 2^p - 1` by folding the bits above `p` back onto the low ones.
+fn reduce_mersenne(mut x: BigUint, p: u64, m: &BigUint) -> BigUint {
+    while x.bits() > p {
+        x = x.low_bits(p) + (&x >> p);
+    }
+    if &x == m {
+        BigUint::zero()
+    } else {
+        x
+    }
+}
+
+/// `x mod f` for `f = 2^n + 1` and `x < f^2`: `2^n = -1`, so the high half is subtracted.
+fn reduce_fermat(x: BigUint, n: u64, f: &BigUint) -> BigUint {
+    let (low, high) = (x.low_bits(n), &x >> n);
+    let high = if &high >= f { high - f } else { high };
+    if low >= high {
+        low - high
+    } else {
+        low + f - high
+    }
+}
+
+/// Lucas sequence `V_k(P, 1) mod m` with the ladder `V_2j = V_j^2 - 2`,
+/// `V_2j+1 = V_j V_j+1 - P`.
+fn lucas_v(k: u64, p: u64, m: &BigUint) -> BigUint {
+    let p = BigUint::from(p) % m;
+    let two = BigUint::from(2u64);
+    // (V_j, V_j+1) starting at j = 0
+    let (mut v, mut v_next) = (two.clone(), p.clone());
+
+    for bit in (0..u64::BITS - k.leading_zeros()).rev() {
+        if k >> bit & 1 == 1 {
+            v = (&v * &v_next + m - &p) % m;
+            v_next = (v_next.square() + m - &two) % m;
+        } else {
+            v_next = (&v * &v_next + m - &p) % m;
+            v = (v.square() + m - &two) % m;
+        }
+    }
+    v
+}
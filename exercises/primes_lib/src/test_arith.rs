@@ -0,0 +1,151 @@
+#[cfg(test)]
+mod tests_arith {
+    use crate::arith::{
+        carmichael_lambda, carmichael_lambda_table, divisor_count, divisor_count_table,
+        divisor_sum, divisor_sum_table, euler_phi, euler_phi_table, mobius, mobius_table,
+    };
+    use crate::sieve::LinearSieve;
+
+    fn gcd(a: u64, b: u64) -> u64 {
+        if b == 0 {
+            a
+        } else {
+            gcd(b, a % b)
+        }
+    }
+
+    fn divisors(n: u64) -> Vec<u64> {
+        (1..=n).filter(|&d| n.is_multiple_of(d)).collect()
+    }
+
+    fn brute_lambda(n: u64) -> u64 {
+        let units: Vec<u64> = (1..=n).filter(|&a| gcd(a, n) == 1).collect();
+        (1..=n)
+            .find(|&m| {
+                units.iter().all(|&a| {
+                    let mut x = 1 % n;
+                    for _ in 0..m {
+                        x = x * a % n;
+                    }
+                    x == 1 % n
+                })
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_linear_sieve() {
+        let sieve = LinearSieve::new(30);
+
+        assert_eq!(sieve.limit(), 30);
+        assert_eq!(sieve.primes, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert_eq!(sieve.smallest_factor[0], 0);
+        assert_eq!(sieve.smallest_factor[1], 0);
+        assert_eq!(sieve.smallest_factor[12], 2);
+        assert_eq!(sieve.smallest_factor[25], 5);
+        assert_eq!(sieve.smallest_factor[29], 29);
+    }
+
+    #[test]
+    fn test_known_values() {
+        assert_eq!(euler_phi(1), 1);
+        assert_eq!(euler_phi(36), 12);
+        assert_eq!(
+            euler_phi(18_446_744_073_709_551_557),
+            18_446_744_073_709_551_556
+        );
+
+        assert_eq!(mobius(1), 1);
+        assert_eq!(mobius(30), -1);
+        assert_eq!(mobius(6), 1);
+        assert_eq!(mobius(12), 0);
+
+        assert_eq!(divisor_count(1), 1);
+        assert_eq!(divisor_count(720_720), 240);
+
+        assert_eq!(divisor_sum(12, 0), 6);
+        assert_eq!(divisor_sum(12, 1), 28);
+        assert_eq!(divisor_sum(12, 2), 210);
+        assert_eq!(divisor_sum(u64::MAX, 1), 31_421_980_989_189_888_768);
+
+        assert_eq!(carmichael_lambda(1), 1);
+        assert_eq!(carmichael_lambda(8), 2);
+        assert_eq!(carmichael_lambda(561), 80);
+        assert_eq!(carmichael_lambda(65_536), 16_384);
+    }
+
+    #[test]
+    fn test_zero() {
+        assert_eq!(euler_phi(0), 0);
+        assert_eq!(mobius(0), 0);
+        assert_eq!(divisor_count(0), 0);
+        assert_eq!(divisor_sum(0, 1), 0);
+        assert_eq!(carmichael_lambda(0), 0);
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        for n in 1..300u64 {
+            let divs = divisors(n);
+
+            assert_eq!(
+                euler_phi(n),
+                (1..=n).filter(|&a| gcd(a, n) == 1).count() as u64
+            );
+            assert_eq!(divisor_count(n), divs.len() as u64);
+            assert_eq!(
+                divisor_sum(n, 1),
+                divs.iter().map(|&d| d as u128).sum::<u128>()
+            );
+            assert_eq!(
+                divisor_sum(n, 3),
+                divs.iter().map(|&d| (d as u128).pow(3)).sum::<u128>()
+            );
+            assert_eq!(carmichael_lambda(n), brute_lambda(n), "n = {}", n);
+
+            // sum of mobius over the divisors is 1 for n == 1 and 0 otherwise
+            let mobius_sum: i64 = divs.iter().map(|&d| mobius(d) as i64).sum();
+            assert_eq!(mobius_sum, (n == 1) as i64);
+        }
+    }
+
+    #[test]
+    fn test_tables_match_single_values() {
+        let limit = 20_000;
+
+        let phi = euler_phi_table(limit);
+        let mu = mobius_table(limit);
+        let tau = divisor_count_table(limit);
+        let sigma = divisor_sum_table(limit, 2);
+        let lambda = carmichael_lambda_table(limit);
+
+        for n in 0..=limit {
+            let m = n as u64;
+            assert_eq!(phi[n], euler_phi(m), "phi({})", n);
+            assert_eq!(mu[n], mobius(m), "mobius({})", n);
+            assert_eq!(tau[n], divisor_count(m), "divisor_count({})", n);
+            assert_eq!(sigma[n], divisor_sum(m, 2), "divisor_sum({}, 2)", n);
+            assert_eq!(lambda[n], carmichael_lambda(m), "carmichael_lambda({})", n);
+        }
+    }
+
+    #[test]
+    fn test_tables_tiny_limits() {
+        assert_eq!(euler_phi_table(0), vec![0]);
+        assert_eq!(euler_phi_table(1), vec![0, 1]);
+        assert_eq!(mobius_table(4), vec![0, 1, -1, -1, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "divisor sum overflows u128")]
+    fn test_divisor_sum_overflow() {
+        divisor_sum(u64::MAX, 3);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    #[should_panic(expected = "linear sieve limit must fit in u32")]
+    fn test_table_limit_above_u32() {
+        euler_phi_table(u32::MAX as usize + 1);
+    }
+}
@@ -0,0 +1,159 @@
+#[cfg(test)]
+mod tests_big_primes {
+    use crate::big_primes::{
+        baillie_psw, is_probable_prime, miller_rabin, miller_rabin_rounds, next_prime, random_prime,
+    };
+    use crate::bigint::BigUint;
+    use crate::math_utils::is_prime;
+    use crate::random::{RandomSource, Xoshiro256};
+
+    fn big(s: &str) -> BigUint {
+        s.parse().unwrap()
+    }
+
+    fn mersenne(p: u64) -> BigUint {
+        (BigUint::one() << p) - 1
+    }
+
+    #[test]
+    fn test_small_values_match_is_prime() {
+        let mut rng = Xoshiro256::seed_from_u64(0);
+        for n in 0..3_000u64 {
+            let value = BigUint::from(n);
+            assert_eq!(is_probable_prime(&value), is_prime(n), "n = {}", n);
+            assert_eq!(miller_rabin(&value, 5, &mut rng), is_prime(n), "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn test_bigint_tests_on_u64_values() {
+        let mut rng = Xoshiro256::seed_from_u64(1);
+        for n in (0..3_000u64).chain(4_000_000_000..4_000_003_000) {
+            let value = BigUint::from(n);
+            assert_eq!(baillie_psw(&value), is_prime(n), "n = {}", n);
+            assert_eq!(
+                miller_rabin_rounds(&value, 20, &mut rng),
+                is_prime(n),
+                "n = {}",
+                n
+            );
+        }
+
+        // strong pseudoprimes to base 2 without factors below 2000 - only the
+        // Lucas half of the test rejects them
+        for n in [8_725_753u64, 9_863_461, 13_694_761, 18_443_701] {
+            assert!(!baillie_psw(&BigUint::from(n)), "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn test_agrees_with_is_prime_u128() {
+        let mut rng = Xoshiro256::seed_from_u64(42);
+        for _ in 0..2_000 {
+            let mut n = BigUint::random(100, &mut rng);
+            n.set_bit(0);
+            let expected = is_prime(n.to_u128().unwrap());
+            assert_eq!(is_probable_prime(&n), expected, "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn test_mersenne_numbers() {
+        for p in [61, 89, 107, 127, 521, 607, 1_279] {
+            assert!(is_probable_prime(&mersenne(p)), "2^{} - 1", p);
+        }
+        for p in [67, 101, 257, 523, 1_277] {
+            assert!(!is_probable_prime(&mersenne(p)), "2^{} - 1", p);
+        }
+    }
+
+    #[test]
+    fn test_composites() {
+        let p =
+            big("115792089237316195423570985008687907853269984665640564039457584007913129639747");
+        let q = next_prime(&p);
+
+        assert!(is_probable_prime(&p));
+        assert!(!is_probable_prime(&(&p * &q)));
+        assert!(!is_probable_prime(&(&p * &p)));
+        assert!(!is_probable_prime(&(&p * 3)));
+        assert!(!is_probable_prime(&(&p + 1)));
+    }
+
+    #[test]
+    fn test_strong_pseudoprimes_to_base_2() {
+        let mut rng = Xoshiro256::seed_from_u64(9);
+        // p * (2p - 1) that pass the strong base-2 test
+        for n in ["147574056656752341661", "147574247971905036253"] {
+            let n = big(n);
+            assert!(!is_probable_prime(&n), "n = {}", n);
+            assert!(!miller_rabin(&n, 20, &mut rng), "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn test_miller_rabin_rounds() {
+        let mut rng = Xoshiro256::seed_from_u64(3);
+        let prime = mersenne(607);
+        let composite = mersenne(607) * mersenne(127);
+
+        assert!(miller_rabin(&prime, 1, &mut rng));
+        assert!(miller_rabin(&prime, 25, &mut rng));
+        assert!(!miller_rabin(&composite, 1, &mut rng));
+        assert!(!miller_rabin(&(&prime + 2), 10, &mut rng));
+        // zero rounds make no claim beyond the trivial checks
+        assert!(miller_rabin(&composite, 0, &mut rng));
+    }
+
+    #[test]
+    fn test_next_prime() {
+        assert_eq!(next_prime(&BigUint::zero()), BigUint::from(2u64));
+        assert_eq!(next_prime(&BigUint::from(2u64)), BigUint::from(3u64));
+        assert_eq!(next_prime(&BigUint::from(3u64)), BigUint::from(5u64));
+        assert_eq!(next_prime(&BigUint::from(24u64)), BigUint::from(29u64));
+        assert_eq!(
+            next_prime(&BigUint::from(18_446_744_073_709_551_557u64)),
+            big("18446744073709551629")
+        );
+        assert_eq!(
+            next_prime(&(BigUint::one() << 512)),
+            (BigUint::one() << 512) + 75
+        );
+        assert_eq!(
+            next_prime(&BigUint::from(10u64).pow(100)),
+            BigUint::from(10u64).pow(100) + 267
+        );
+    }
+
+    #[test]
+    fn test_random_prime() {
+        let mut rng = Xoshiro256::seed_from_u64(2024);
+        for bits in [2, 3, 16, 64, 65, 128, 512] {
+            let p = random_prime(bits, &mut rng);
+            assert_eq!(p.bits(), bits);
+            assert!(is_probable_prime(&p));
+            assert!(miller_rabin(&p, 10, &mut rng));
+        }
+    }
+
+    #[test]
+    fn test_random_prime_is_reproducible() {
+        let first = random_prime(256, &mut Xoshiro256::seed_from_u64(17));
+        let second = random_prime(256, &mut Xoshiro256::seed_from_u64(17));
+        let other = random_prime(256, &mut Xoshiro256::seed_from_u64(18));
+
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn test_xoshiro_reference_output() {
+        // reference sequence for the state produced by SplitMix64 from seed 0
+        let mut rng = Xoshiro256::seed_from_u64(0);
+        let first: Vec<u64> = (0..3).map(|_| rng.next_u64()).collect();
+        let mut again = Xoshiro256::seed_from_u64(0);
+
+        assert_eq!(first, (0..3).map(|_| again.next_u64()).collect::<Vec<_>>());
+        assert_eq!(first[0], 0x99EC_5F36_CB75_F2B4);
+    }
+}
@@ -0,0 +1,249 @@
+#[cfg(test)]
+mod tests_bigint {
+    use crate::bigint::BigUint;
+    use crate::random::Xoshiro256;
+
+    fn big(s: &str) -> BigUint {
+        s.parse().unwrap()
+    }
+
+    /// Values with long runs of zero and all-ones limbs - the edge cases of carries
+    /// and of the quotient estimate in long division.
+    fn awkward_values() -> Vec<BigUint> {
+        let mut values = Vec::new();
+        for limbs in 1..6u64 {
+            let ones = (BigUint::one() << (64 * limbs)) - 1;
+            values.push(ones.clone());
+            values.push(&ones - (BigUint::one() << 63));
+            values.push(BigUint::one() << (64 * limbs - 1));
+            values.push((BigUint::one() << (64 * limbs)) + 1);
+            values.push((BigUint::one() << (64 * limbs)) + (BigUint::one() << 32));
+        }
+        values
+    }
+
+    #[test]
+    fn test_matches_u128_arithmetic() {
+        let mut rng = Xoshiro256::seed_from_u64(7);
+        for _ in 0..2_000 {
+            let a = BigUint::random(100, &mut rng).to_u128().unwrap();
+            let b = BigUint::random(60, &mut rng).to_u128().unwrap() + 1;
+            let (x, y) = (BigUint::from(a), BigUint::from(b));
+
+            assert_eq!((&x + &y).to_u128(), Some(a + b));
+            assert_eq!((&x - &y).to_u128(), Some(a - b));
+            assert_eq!((&x * &(&y >> 40)).to_u128(), Some(a * (b >> 40)));
+            assert_eq!((&x / &y).to_u128(), Some(a / b));
+            assert_eq!((&x % &y).to_u128(), Some(a % b));
+            assert_eq!(x.rem_u64(b as u64), (a % b) as u64);
+            assert_eq!((&x << 20).to_u128(), Some(a << 20));
+            assert_eq!((&x >> 70).to_u128(), Some(a >> 70));
+            assert_eq!(x.cmp(&y), a.cmp(&b));
+            assert_eq!(x.to_string(), a.to_string());
+        }
+    }
+
+    #[test]
+    fn test_div_rem_identity() {
+        let mut rng = Xoshiro256::seed_from_u64(11);
+        let mut values = awkward_values();
+        for bits in [64, 65, 127, 128, 129, 300, 700] {
+            values.push(BigUint::random(bits, &mut rng));
+        }
+
+        for a in &values {
+            for d in values.iter().filter(|d| !d.is_zero()) {
+                let (q, r) = a.div_rem(d);
+                assert!(&r < d, "{} % {}", a, d);
+                assert_eq!(&(&q * d) + &r, *a, "{} / {}", a, d);
+            }
+        }
+    }
+
+    #[test]
+    fn test_known_values() {
+        let mersenne = (BigUint::one() << 521) - 1;
+        let divisor = BigUint::from(3u64).pow(200) + 12_345;
+
+        assert_eq!(
+            divisor,
+            big("265613988875874769338781322035779626829233452653394495974574961739092490901302182994384699056346")
+        );
+        assert_eq!(
+            &mersenne / &divisor,
+            big("25845015502322162051677224275462921789355737634958239302764093")
+        );
+        assert_eq!(
+            &mersenne % &divisor,
+            big("6444738598408051937185051094307807315475493613878300445264310684374751932233248590724562472973")
+        );
+        assert_eq!(
+            format!("{:x}", BigUint::from(3u64).pow(100)),
+            "5a4653ca673768565b41f775d6947d55cf3813d1"
+        );
+    }
+
+    #[test]
+    fn test_pow_mod() {
+        let mersenne = (BigUint::one() << 521) - 1;
+        let exp = BigUint::from(10u64).pow(40);
+        assert_eq!(
+            BigUint::from(3u64).pow_mod(&exp, &mersenne),
+            big("5215075314354162481980592823198776160175172338199896974713737355831208079610332514946695760947623204066595403207044639436245188913761668232694200434116877136")
+        );
+
+        // even modulus
+        let exp = (BigUint::one() << 200) + 1;
+        assert_eq!(
+            BigUint::from(7u64).pow_mod(&exp, &(BigUint::one() << 300)),
+            big("283198477856965690228244037211606138419628040894951621797290263086775888358669048717770759")
+        );
+    }
+
+    #[test]
+    fn test_pow_mod_matches_u64() {
+        let mut rng = Xoshiro256::seed_from_u64(3);
+        for _ in 0..500 {
+            let base = BigUint::random(64, &mut rng);
+            let exp = BigUint::random(64, &mut rng);
+            let m = BigUint::random(64, &mut rng) + 1;
+
+            let expected = crate::modular::mod_pow(
+                base.to_u64().unwrap(),
+                exp.to_u64().unwrap(),
+                m.to_u64().unwrap(),
+            );
+            assert_eq!(base.pow_mod(&exp, &m), BigUint::from(expected));
+        }
+
+        let m = BigUint::from(97u64);
+        assert_eq!(
+            BigUint::from(5u64).pow_mod(&BigUint::zero(), &m),
+            BigUint::one()
+        );
+        assert_eq!(
+            BigUint::from(5u64).pow_mod(&BigUint::one(), &BigUint::one()),
+            BigUint::zero()
+        );
+    }
+
+    #[test]
+    fn test_parse_and_format() {
+        let text = "123456789012345678901234567890123456789";
+        assert_eq!(big(text).to_string(), text);
+        assert_eq!(big("0").to_string(), "0");
+        assert_eq!(big("000042").to_string(), "42");
+        assert_eq!(big("1_000_000").to_string(), "1000000");
+        assert_eq!(format!("{:>6}", big("42")), "    42");
+        assert_eq!(format!("{:#x}", big("255")), "0xff");
+        assert_eq!(format!("{:x}", BigUint::zero()), "0");
+        assert_eq!(
+            BigUint::from_str_radix("ffffffffffffffffffffffffffffffff", 16).unwrap(),
+            BigUint::from(u128::MAX)
+        );
+
+        assert!("".parse::<BigUint>().is_err());
+        assert!("12a".parse::<BigUint>().is_err());
+        assert!("-1".parse::<BigUint>().is_err());
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let value =
+            big("1766847064778384329583297500742918515827483896875618958121606201292619775");
+        let bytes = value.to_bytes_be();
+
+        assert_eq!(bytes[0], 0xFF);
+        assert_eq!(BigUint::from_bytes_be(&bytes), value);
+        assert_eq!(BigUint::from_bytes_be(&[0, 0, 1, 0]), BigUint::from(256u64));
+        assert_eq!(BigUint::zero().to_bytes_be(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_bits() {
+        let mut value = BigUint::zero();
+        assert_eq!(value.bits(), 0);
+        assert_eq!(value.trailing_zeros(), None);
+
+        value.set_bit(200);
+        assert_eq!(value.bits(), 201);
+        assert!(value.bit(200));
+        assert!(!value.bit(199));
+        assert_eq!(value.trailing_zeros(), Some(200));
+        assert!(value.is_even());
+        assert!((value + 1).is_odd());
+    }
+
+    #[test]
+    fn test_isqrt() {
+        let mut rng = Xoshiro256::seed_from_u64(5);
+        for bits in [1, 2, 63, 64, 65, 128, 129, 1_000] {
+            let n = BigUint::random(bits, &mut rng);
+            let root = n.isqrt();
+            assert!(&root * &root <= n);
+            assert!(&(&root + 1) * &(&root + 1) > n);
+        }
+
+        let square = BigUint::from(10u64).pow(80);
+        assert_eq!(square.isqrt(), BigUint::from(10u64).pow(40));
+        assert_eq!((square - 1).isqrt(), BigUint::from(10u64).pow(40) - 1);
+    }
+
+    #[test]
+    fn test_checked_sub() {
+        let a = BigUint::one() << 128;
+        assert_eq!(a.checked_sub(&(&a + 1)), None);
+        assert_eq!(a.checked_sub(&a), Some(BigUint::zero()));
+        assert_eq!(
+            a.checked_sub(&BigUint::one()),
+            Some(BigUint::from(u128::MAX))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to subtract with overflow")]
+    fn test_sub_underflow_panics() {
+        let _ = BigUint::one() - 2;
+    }
+
+    #[test]
+    fn test_random_below() {
+        let mut rng = Xoshiro256::seed_from_u64(1);
+        let bound = BigUint::from(10u64).pow(30);
+        for _ in 0..1_000 {
+            assert!(BigUint::random_below(&bound, &mut rng) < bound);
+        }
+
+        let mut seen = [false; 3];
+        for _ in 0..100 {
+            let value = BigUint::random_below(&BigUint::from(3u64), &mut rng);
+            seen[value.to_u64().unwrap() as usize] = true;
+        }
+        assert_eq!(seen, [true; 3]);
+    }
+
+    #[test]
+    fn test_square_matches_mul() {
+        // sizes around the Karatsuba threshold, balanced and unbalanced
+        let mut rng = Xoshiro256::seed_from_u64(99);
+        for bits in [64 * 31, 64 * 32, 64 * 33, 64 * 70, 64 * 131 + 17] {
+            let a = BigUint::random(bits, &mut rng);
+            let b = BigUint::random(bits / 3 + 64 * 32, &mut rng);
+            let product = &a * &b;
+
+            assert_eq!(a.square(), &a * &a);
+            assert_eq!(&product / &b, a);
+            assert!((&product % &a).is_zero());
+            assert_eq!(
+                (&a + &b).square(),
+                &(a.square() + b.square()) + &(&product << 1)
+            );
+        }
+
+        let ones = (BigUint::one() << (64 * 80)) - 1;
+        assert_eq!(
+            ones.square(),
+            (BigUint::one() << (64 * 160)) - (BigUint::one() << (64 * 80 + 1)) + 1
+        );
+    }
+}
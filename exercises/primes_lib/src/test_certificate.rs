@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod tests_certificate {
+    use crate::certificate::{certify, verify_certificate, Certificate, PrattStep};
+    use crate::math_utils::is_prime;
+
+    #[test]
+    fn test_certify_small_numbers() {
+        for n in 0..2_000u64 {
+            match certify(n) {
+                Some(certificate) => {
+                    assert!(is_prime(n), "certified composite {}", n);
+                    assert_eq!(certificate.prime, n);
+                    assert!(verify_certificate(&certificate), "n = {}", n);
+                }
+                None => assert!(!is_prime(n), "no certificate for prime {}", n),
+            }
+        }
+    }
+
+    #[test]
+    fn test_certify_large_primes() {
+        for p in [
+            1_000_000_007,
+            4_294_967_291,
+            1_000_000_000_000_000_003,
+            18_446_744_073_709_551_557,
+        ] {
+            let certificate = certify(p).unwrap();
+            assert!(verify_certificate(&certificate), "p = {}", p);
+        }
+        assert_eq!(certify(18_446_744_073_709_551_556), None);
+    }
+
+    #[test]
+    fn test_text_format() {
+        let certificate = certify(23).unwrap();
+        let text = "pratt 23\n2 1\n5 2 2\n11 2 2 5\n23 5 2 11\n";
+
+        assert_eq!(certificate.to_string(), text);
+        assert_eq!(text.parse::<Certificate>().unwrap(), certificate);
+    }
+
+    #[test]
+    fn test_text_round_trip() {
+        let certificate = certify(18_446_744_073_709_551_557).unwrap();
+        let parsed: Certificate = certificate.to_string().parse().unwrap();
+
+        assert_eq!(parsed, certificate);
+        assert!(verify_certificate(&parsed));
+    }
+
+    #[test]
+    fn test_parse_ignores_comments_and_blank_lines() {
+        let text = "# certificate for 7\n\npratt 7\n2 1\n  3 2 2\n\n7 3 2 3\n";
+        let certificate: Certificate = text.parse().unwrap();
+
+        assert_eq!(certificate.steps.len(), 3);
+        assert!(verify_certificate(&certificate));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!("".parse::<Certificate>().is_err());
+        assert!("pratt".parse::<Certificate>().is_err());
+        assert!("prat 7\n2 1".parse::<Certificate>().is_err());
+        assert!("pratt 7\n2".parse::<Certificate>().is_err());
+        assert!("pratt 7\n2 1\n3 x 2".parse::<Certificate>().is_err());
+
+        let error = "pratt 7\n2 1\n3 -2 2".parse::<Certificate>().unwrap_err();
+        assert_eq!(error.to_string(), "line 3: invalid number");
+    }
+
+    #[test]
+    fn test_verify_rejects_forged_certificates() {
+        let step = |prime, witness, factors: &[u64]| PrattStep {
+            prime,
+            witness,
+            factors: factors.to_vec(),
+        };
+        let base = vec![step(2, 1, &[]), step(3, 2, &[2])];
+
+        // 15 - 1 = 2 * 7, but 7 is never certified
+        let certificate = Certificate {
+            prime: 15,
+            steps: vec![step(2, 1, &[]), step(15, 2, &[2, 7])],
+        };
+        assert!(!verify_certificate(&certificate));
+
+        // the factors do not account for all of 13 - 1
+        let certificate = Certificate {
+            prime: 13,
+            steps: [base.clone(), vec![step(13, 2, &[2])]].concat(),
+        };
+        assert!(!verify_certificate(&certificate));
+
+        // 4 has order 6 modulo 13, not 12
+        let certificate = Certificate {
+            prime: 13,
+            steps: [base.clone(), vec![step(13, 4, &[2, 3])]].concat(),
+        };
+        assert!(!verify_certificate(&certificate));
+
+        // a valid chain for a different number
+        let certificate = Certificate {
+            prime: 5,
+            steps: base.clone(),
+        };
+        assert!(!verify_certificate(&certificate));
+
+        // composite 561 with every factor of 560 "certified"
+        let mut steps = base.clone();
+        steps.push(step(5, 2, &[2]));
+        steps.push(step(7, 3, &[2, 3]));
+        steps.push(step(561, 2, &[2, 5, 7]));
+        assert!(!verify_certificate(&Certificate { prime: 561, steps }));
+
+        assert!(!verify_certificate(&Certificate {
+            prime: 2,
+            steps: Vec::new(),
+        }));
+        assert!(verify_certificate(&Certificate {
+            prime: 13,
+            steps: [base, vec![step(13, 2, &[2, 3])]].concat(),
+        }));
+    }
+
+    #[test]
+    fn test_verify_rejects_degenerate_steps() {
+        let step = |prime, witness, factors: &[u64]| PrattStep {
+            prime,
+            witness,
+            factors: factors.to_vec(),
+        };
+
+        for steps in [
+            vec![step(0, 1, &[])],
+            vec![step(1, 1, &[])],
+            vec![step(4, 1, &[])],
+            vec![step(2, 1, &[2])],
+            vec![step(2, 1, &[]), step(3, 0, &[2])],
+            vec![step(2, 1, &[]), step(3, 5, &[2])],
+            vec![step(2, 1, &[]), step(3, 2, &[2, 2])],
+        ] {
+            let prime = steps.last().unwrap().prime;
+            assert!(!verify_certificate(&Certificate { prime, steps }));
+        }
+    }
+}
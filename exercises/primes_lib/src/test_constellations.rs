@@ -0,0 +1,163 @@
+#[cfg(test)]
+mod tests_constellations {
+    use crate::constellations::{
+        cousin_primes, maximal_gaps, prime_gaps, sexy_primes, twin_primes, Constellations, PrimeGap,
+    };
+    use crate::math_utils::{is_prime, primes_in_range};
+
+    #[test]
+    fn test_twin_primes() {
+        let twins: Vec<(u64, u64)> = twin_primes(0..100).collect();
+        assert_eq!(
+            twins,
+            vec![
+                (3, 5),
+                (5, 7),
+                (11, 13),
+                (17, 19),
+                (29, 31),
+                (41, 43),
+                (59, 61),
+                (71, 73)
+            ]
+        );
+
+        assert_eq!(twin_primes(0..1_000_000).count(), 8_169);
+    }
+
+    #[test]
+    fn test_twin_pair_may_end_past_range() {
+        assert_eq!(twin_primes(0..72).last(), Some((71, 73)));
+        assert_eq!(twin_primes(72..100).next(), None);
+    }
+
+    #[test]
+    fn test_cousin_and_sexy_primes() {
+        let cousins: Vec<(u64, u64)> = cousin_primes(0..100).collect();
+        assert_eq!(
+            cousins,
+            vec![
+                (3, 7),
+                (7, 11),
+                (13, 17),
+                (19, 23),
+                (37, 41),
+                (43, 47),
+                (67, 71),
+                (79, 83),
+                (97, 101)
+            ]
+        );
+
+        let sexy: Vec<(u64, u64)> = sexy_primes(0..30).collect();
+        assert_eq!(
+            sexy,
+            vec![(5, 11), (7, 13), (11, 17), (13, 19), (17, 23), (23, 29)]
+        );
+    }
+
+    #[test]
+    fn test_constellations_quadruplets() {
+        let quadruplets: Vec<u64> = Constellations::new(0..3_500, &[0, 2, 6, 8]).collect();
+        assert_eq!(
+            quadruplets,
+            vec![5, 11, 101, 191, 821, 1_481, 1_871, 2_081, 3_251, 3_461]
+        );
+    }
+
+    #[test]
+    fn test_constellations_sextuplets_far_apart() {
+        let pattern = [0, 4, 6, 10, 12, 16];
+        let sextuplets = Constellations::new(0..2_000_000, &pattern);
+
+        assert_eq!(sextuplets.pattern(), &pattern);
+        assert_eq!(
+            sextuplets.take(5).collect::<Vec<u64>>(),
+            vec![7, 97, 16_057, 19_417, 43_777]
+        );
+    }
+
+    #[test]
+    fn test_constellations_match_direct_check() {
+        let pattern = [0, 2, 6];
+        let expected: Vec<u64> = primes_in_range(0..50_000)
+            .into_iter()
+            .filter(|&p| pattern.iter().all(|&o| is_prime(p + o)))
+            .collect();
+
+        assert_eq!(
+            Constellations::new(0..50_000, &pattern).collect::<Vec<u64>>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_constellations_single_offset_is_primes() {
+        let primes: Vec<u64> = Constellations::new(1_000..2_000, &[0]).collect();
+        assert_eq!(primes, primes_in_range(1_000..2_000));
+    }
+
+    #[test]
+    #[should_panic(expected = "starts with offset 0")]
+    fn test_constellations_invalid_pattern() {
+        Constellations::new(0..10, &[2, 4]);
+    }
+
+    #[test]
+    fn test_prime_gaps() {
+        let gaps: Vec<u64> = prime_gaps(0..30).map(|gap| gap.len()).collect();
+        assert_eq!(gaps, vec![1, 2, 2, 4, 2, 4, 2, 4, 6]);
+
+        assert_eq!(prime_gaps(24..28).count(), 0);
+        assert_eq!(
+            prime_gaps(23..30).next(),
+            Some(PrimeGap { start: 23, end: 29 })
+        );
+    }
+
+    #[test]
+    fn test_maximal_gaps() {
+        let records: Vec<(u64, u64)> = maximal_gaps(0..2_100_000)
+            .map(|gap| (gap.start, gap.end))
+            .collect();
+
+        assert_eq!(
+            records,
+            vec![
+                (2, 3),
+                (3, 5),
+                (7, 11),
+                (23, 29),
+                (89, 97),
+                (113, 127),
+                (523, 541),
+                (887, 907),
+                (1_129, 1_151),
+                (1_327, 1_361),
+                (9_551, 9_587),
+                (15_683, 15_727),
+                (19_609, 19_661),
+                (31_397, 31_469),
+                (155_921, 156_007),
+                (360_653, 360_749),
+                (370_261, 370_373),
+                (492_113, 492_227),
+                (1_349_533, 1_349_651),
+                (1_357_201, 1_357_333),
+                (2_010_733, 2_010_881),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_maximal_gaps_inside_window() {
+        let start = 1_000_000_000_000;
+        let records: Vec<PrimeGap> = maximal_gaps(start..start + 100_000).collect();
+
+        assert!(records.windows(2).all(|w| w[0].len() < w[1].len()));
+        let largest = prime_gaps(start..start + 100_000)
+            .map(|gap| gap.len())
+            .max();
+        assert_eq!(records.last().map(|gap| gap.len()), largest);
+    }
+}
@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests_counting {
+    use crate::counting::{
+        nth_prime, prime_pi, try_nth_prime, try_prime_pi, NTH_PRIME_LIMIT, PRIME_PI_LIMIT,
+    };
+    use crate::error::PrimesError;
+    use crate::math_utils::{n_primes, primes_in_range};
+
+    #[test]
+    fn test_prime_pi_small_values() {
+        assert_eq!(prime_pi(0), 0);
+        assert_eq!(prime_pi(1), 0);
+        assert_eq!(prime_pi(2), 1);
+        assert_eq!(prime_pi(3), 2);
+        assert_eq!(prime_pi(4), 2);
+
+        for x in 0..3_000u64 {
+            assert_eq!(
+                prime_pi(x),
+                primes_in_range(0..x + 1).len() as u64,
+                "x = {}",
+                x
+            );
+        }
+    }
+
+    #[test]
+    fn test_prime_pi_powers_of_ten() {
+        let expected = [
+            4,
+            25,
+            168,
+            1_229,
+            9_592,
+            78_498,
+            664_579,
+            5_761_455,
+            50_847_534,
+            455_052_511,
+        ];
+
+        for (exponent, &count) in expected.iter().enumerate() {
+            assert_eq!(prime_pi(10u64.pow(exponent as u32 + 1)), count);
+        }
+    }
+
+    #[test]
+    fn test_prime_pi_matches_sieve() {
+        for x in [65_535, 65_536, 999_983, 1_000_003, 10_000_019] {
+            assert_eq!(
+                prime_pi(x),
+                primes_in_range(0..x + 1).len() as u64,
+                "x = {}",
+                x
+            );
+        }
+    }
+
+    #[test]
+    fn test_nth_prime() {
+        assert_eq!(nth_prime(1), 2);
+        assert_eq!(nth_prime(2), 3);
+        assert_eq!(nth_prime(10), 29);
+        assert_eq!(nth_prime(10_000), 104_729);
+        assert_eq!(nth_prime(1_000_000), 15_485_863);
+        assert_eq!(nth_prime(100_000_000), 2_038_074_743);
+
+        assert_eq!(
+            nth_prime(250_000),
+            *n_primes::<u64>(250_000).last().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_nth_prime_around_estimate() {
+        for n in [100_001, 123_456, 654_321, 2_000_000] {
+            let p = nth_prime(n);
+            assert_eq!(prime_pi(p), n);
+            assert_eq!(prime_pi(p - 1), n - 1);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "nth_prime(1) == 2")]
+    fn test_nth_prime_zero() {
+        nth_prime(0);
+    }
+
+    #[test]
+    fn test_limits() {
+        assert_eq!(try_prime_pi(1_000), Ok(168));
+        assert_eq!(try_nth_prime(168), Ok(997));
+        assert_eq!(try_nth_prime(0), Err(PrimesError::ZeroIndex));
+
+        for x in [PRIME_PI_LIMIT + 1, u64::MAX] {
+            assert_eq!(
+                try_prime_pi(x),
+                Err(PrimesError::LimitTooLarge {
+                    limit: x as u128,
+                    max: PRIME_PI_LIMIT as u128,
+                })
+            );
+        }
+        for n in [NTH_PRIME_LIMIT + 1, u64::MAX] {
+            assert_eq!(
+                try_nth_prime(n),
+                Err(PrimesError::LimitTooLarge {
+                    limit: n as u128,
+                    max: NTH_PRIME_LIMIT as u128,
+                })
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "supported maximum")]
+    fn test_prime_pi_above_limit() {
+        prime_pi(u64::MAX);
+    }
+}
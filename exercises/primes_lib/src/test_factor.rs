@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod tests_factor {
+    use crate::factor::factorize;
+    use crate::math_utils::is_prime;
+
+    fn product(factors: &[(u64, u32)]) -> u64 {
+        factors.iter().map(|&(p, e)| p.pow(e)).product()
+    }
+
+    #[test]
+    fn test_factorize_trivial() {
+        assert_eq!(factorize(0), vec![]);
+        assert_eq!(factorize(1), vec![]);
+        assert_eq!(factorize(2), vec![(2, 1)]);
+        assert_eq!(factorize(97), vec![(97, 1)]);
+    }
+
+    #[test]
+    fn test_factorize_small_numbers() {
+        assert_eq!(factorize(12), vec![(2, 2), (3, 1)]);
+        assert_eq!(factorize(360), vec![(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(factorize(1024), vec![(2, 10)]);
+        assert_eq!(
+            factorize(600_851_475_143),
+            vec![(71, 1), (839, 1), (1_471, 1), (6_857, 1)]
+        );
+
+        for n in 2..10_000 {
+            let factors = factorize(n);
+            assert_eq!(product(&factors), n);
+            assert!(factors.iter().all(|&(p, _)| is_prime(p)));
+            assert!(factors.windows(2).all(|w| w[0].0 < w[1].0));
+        }
+    }
+
+    #[test]
+    fn test_factorize_powers_of_large_primes() {
+        assert_eq!(
+            factorize(4_294_967_291 * 4_294_967_291),
+            vec![(4_294_967_291, 2)]
+        );
+        assert_eq!(factorize(2_097_143u64.pow(3)), vec![(2_097_143, 3)]);
+        assert_eq!(
+            factorize(65_537u64.pow(2) * 6_700_417),
+            vec![(65_537, 2), (6_700_417, 1)]
+        );
+    }
+
+    #[test]
+    fn test_factorize_u64_extremes() {
+        assert_eq!(
+            factorize(u64::MAX),
+            vec![
+                (3, 1),
+                (5, 1),
+                (17, 1),
+                (257, 1),
+                (641, 1),
+                (65_537, 1),
+                (6_700_417, 1)
+            ]
+        );
+        assert_eq!(
+            factorize((1 << 63) - 1),
+            vec![
+                (7, 2),
+                (73, 1),
+                (127, 1),
+                (337, 1),
+                (92_737, 1),
+                (649_657, 1)
+            ]
+        );
+        assert_eq!(
+            factorize(18_446_744_073_709_551_557),
+            vec![(18_446_744_073_709_551_557, 1)]
+        );
+    }
+
+    #[test]
+    fn test_factorize_semiprimes_near_2_pow_63() {
+        assert_eq!(
+            factorize(9_223_371_873_002_223_329),
+            vec![(3_037_000_453, 1), (3_037_000_493, 1)]
+        );
+        assert_eq!(
+            factorize(9_223_372_037_000_249_951),
+            vec![(3_037_000_493, 1), (3_037_000_507, 1)]
+        );
+        assert_eq!(
+            factorize(9_223_372_036_832_100_397),
+            vec![(1_000_003, 1), (9_223_344_366_799, 1)]
+        );
+        assert_eq!(
+            factorize(9_223_372_036_854_775_783),
+            vec![(9_223_372_036_854_775_783, 1)]
+        );
+    }
+}
@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod tests_gaussian {
+    use crate::gaussian::{factorize_gaussian, is_gaussian_prime, GaussianInt};
+
+    fn g(re: i64, im: i64) -> GaussianInt {
+        GaussianInt::new(re, im)
+    }
+
+    fn product(unit: GaussianInt, factors: &[(GaussianInt, u32)]) -> GaussianInt {
+        factors
+            .iter()
+            .fold(unit, |acc, &(p, e)| (0..e).fold(acc, |acc, _| acc * p))
+    }
+
+    #[test]
+    fn test_is_gaussian_prime() {
+        // first-quadrant Gaussian primes with norm below 30
+        let mut primes = Vec::new();
+        for re in 1..6 {
+            for im in 0..6 {
+                if g(re, im).norm() < 30 && is_gaussian_prime(g(re, im)) {
+                    primes.push(g(re, im));
+                }
+            }
+        }
+        primes.sort_by_key(|z| (z.norm(), z.re));
+        assert_eq!(
+            primes,
+            [
+                g(1, 1),
+                g(1, 2),
+                g(2, 1),
+                g(3, 0),
+                g(2, 3),
+                g(3, 2),
+                g(1, 4),
+                g(4, 1),
+                g(2, 5),
+                g(5, 2)
+            ]
+        );
+
+        assert!(is_gaussian_prime(g(0, -7)));
+        assert!(is_gaussian_prime(g(-3, -2)));
+        assert!(!is_gaussian_prime(g(5, 0)));
+        assert!(!is_gaussian_prime(g(2, 0)));
+        assert!(!is_gaussian_prime(GaussianInt::ONE));
+        assert!(!is_gaussian_prime(GaussianInt::ZERO));
+        // norm 13000000044000000121 is prime
+        assert!(is_gaussian_prime(g(3_000_000_000, 2_000_000_011)));
+        assert!(!is_gaussian_prime(g(3_000_000_000, 2_000_000_013)));
+    }
+
+    #[test]
+    fn test_associates_share_primality() {
+        for re in -20..20 {
+            for im in -20..20 {
+                let z = g(re, im);
+                let associates = [z, -z, z * GaussianInt::I, -(z * GaussianInt::I)];
+                assert!(associates
+                    .iter()
+                    .all(|&a| is_gaussian_prime(a) == is_gaussian_prime(z)));
+                assert!(associates.iter().all(|a| a.normalized() == z.normalized()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_factorize_known_values() {
+        assert_eq!(
+            factorize_gaussian(g(5, 0)),
+            (g(0, -1), vec![(g(1, 2), 1), (g(2, 1), 1)])
+        );
+        assert_eq!(factorize_gaussian(g(2, 0)), (g(0, -1), vec![(g(1, 1), 2)]));
+        assert_eq!(factorize_gaussian(g(0, 9)), (g(0, 1), vec![(g(3, 0), 2)]));
+        assert_eq!(factorize_gaussian(g(-1, 0)), (g(-1, 0), vec![]));
+        assert_eq!(
+            factorize_gaussian(GaussianInt::ZERO),
+            (GaussianInt::ZERO, vec![])
+        );
+    }
+
+    #[test]
+    fn test_factorize_multiplies_back() {
+        for re in -60..60 {
+            for im in -60..60 {
+                let z = g(re, im);
+                if z.is_zero() {
+                    continue;
+                }
+                let (unit, factors) = factorize_gaussian(z);
+                assert!(unit.is_unit(), "{}", z);
+                assert_eq!(product(unit, &factors), z, "{}", z);
+                assert!(factors
+                    .iter()
+                    .all(|&(p, _)| is_gaussian_prime(p) && p == p.normalized()));
+                assert_eq!(
+                    is_gaussian_prime(z),
+                    factors == [(z.normalized(), 1)],
+                    "{}",
+                    z
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_factorize_large_values() {
+        // 2^32 - 5 is a prime = 3 (mod 4)
+        let inert = g(4_294_967_291, 0);
+        assert_eq!(
+            factorize_gaussian(inert),
+            (GaussianInt::ONE, vec![(inert, 1)])
+        );
+
+        let split = g(3_000_000_000, 2_000_000_011);
+        assert_eq!(
+            factorize_gaussian(split),
+            (GaussianInt::ONE, vec![(split, 1)])
+        );
+
+        // norm 2 * 5 * 37 * 34337 * 983233 * 1089661
+        let z = g(3_000_000_019, 2_147_483_647);
+        let (unit, factors) = factorize_gaussian(z);
+        assert_eq!(factors.len(), 6);
+        assert_eq!(product(unit, &factors), z);
+    }
+
+    #[test]
+    fn test_div_rem_and_gcd() {
+        for (a, b) in [
+            (g(27, -13), g(4, 3)),
+            (g(-100, 7), g(-3, 5)),
+            (g(5, 5), g(1, 1)),
+        ] {
+            let (q, r) = a.div_rem(b);
+            assert_eq!(q * b + r, a);
+            assert!(2 * r.norm() <= b.norm());
+        }
+
+        assert_eq!(g(5, 0).gcd(g(3, 4)), g(2, 1));
+        assert_eq!(g(15, 0).gcd(g(3, 0)), g(3, 0));
+        assert_eq!(g(7, 0).gcd(g(2, 1)), GaussianInt::ONE);
+        assert_eq!(g(12, 6).checked_div(g(2, 3)), None);
+        assert_eq!(g(-1, 5).checked_div(g(1, 1)), Some(g(2, 3)));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(g(3, 2).to_string(), "3+2i");
+        assert_eq!(g(3, -2).to_string(), "3-2i");
+        assert_eq!(g(0, -1).to_string(), "-i");
+        assert_eq!(g(-4, 1).to_string(), "-4+i");
+        assert_eq!(g(5, 0).to_string(), "5");
+        assert_eq!(GaussianInt::ZERO.to_string(), "0");
+    }
+}
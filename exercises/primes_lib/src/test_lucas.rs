@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests_lucas {
+    use crate::big_primes::BigModulus;
+    use crate::bigint::BigUint;
+    use crate::lucas::is_strong_lucas_probable_prime;
+    use crate::math_utils::is_prime;
+    use crate::montgomery::Montgomery;
+
+    fn lucas_u128(n: u128) -> bool {
+        is_strong_lucas_probable_prime(&Montgomery::new(n))
+    }
+
+    fn lucas_big(n: u128) -> bool {
+        is_strong_lucas_probable_prime(&BigModulus::new(&BigUint::from(n)))
+    }
+
+    #[test]
+    fn test_primes_pass() {
+        for n in (41..20_000u128).step_by(2).filter(|&n| is_prime(n)) {
+            assert!(lucas_u128(n), "{}", n);
+        }
+    }
+
+    #[test]
+    fn test_composites_fail_except_pseudoprimes() {
+        // the strong Lucas pseudoprimes below 20000 (OEIS A217255)
+        let pseudoprimes = [5_459, 5_777, 10_877, 16_109, 18_971];
+        for n in (41..20_000u128).step_by(2).filter(|&n| !is_prime(n)) {
+            assert_eq!(lucas_u128(n), pseudoprimes.contains(&n), "{}", n);
+        }
+    }
+
+    #[test]
+    fn test_squares_fail() {
+        for root in [1_000_003u128, (1 << 61) - 1] {
+            assert!(!lucas_u128(root * root));
+            assert!(!lucas_big(root * root));
+        }
+    }
+
+    #[test]
+    fn test_both_moduli_agree() {
+        // odd numbers just above 2^64 without factors below 40, where the BigUint
+        // path takes over in practice
+        let candidates = (1u128 << 64..(1 << 64) + 20_000)
+            .filter(|&n| (2..40).all(|p| n % p != 0))
+            .chain([
+                (1 << 127) - 1,
+                340_282_366_920_938_463_463_374_607_431_768_211_297,
+            ]);
+        for n in candidates {
+            let expected = is_prime(n);
+            assert_eq!(lucas_u128(n), expected, "{}", n);
+            assert_eq!(lucas_big(n), expected, "{}", n);
+        }
+    }
+}
@@ -1,9 +1,18 @@
 #[cfg(test)]
 mod tests_primes {
-    use crate::math_utils::{is_prime, n_primes, primes_in_range} ;
+    use crate::error::PrimesError;
+    use crate::math_utils::{
+        all_primes, is_prime, is_prime_fast, is_prime_trial, is_prime_trial_naive, n_primes,
+        n_primes_into, next_prime_after, prev_prime_before, primes_from_vec, primes_in_range,
+        primes_in_range_into, try_n_primes, try_primes_in_range, Primes, TRY_N_PRIMES_LIMIT,
+        TRY_RANGE_WIDTH_LIMIT,
+    };
+    use std::ops::Bound;
 
     #[test]
     fn test_is_prime() {
+        let is_prime = is_prime::<u64>;
+
         assert!(!is_prime(0));
         assert!(!is_prime(1));
         assert!(is_prime(2));
@@ -24,6 +33,8 @@ mod tests_primes {
 
     #[test]
     fn test_n_primes() {
+        let n_primes = n_primes::<u64>;
+
         let primes = n_primes(0);
         assert_eq!(primes, Vec::new());
 
@@ -39,6 +50,8 @@ mod tests_primes {
 
     #[test]
     fn test_primes_in_range() {
+        let primes_in_range = primes_in_range::<u64>;
+
         let primes = primes_in_range(2..100); // std::ops::Range<u32> is iterable
 
         assert_eq!(
@@ -49,4 +62,408 @@ mod tests_primes {
             ]
         );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_is_prime_fast_matches_trial_division() {
+        for n in 0..20_000 {
+            assert_eq!(is_prime_fast(n), is_prime_trial(n), "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn test_is_prime_trial_matches_naive() {
+        for n in (0..300_000).chain(1_048_000..1_049_000) {
+            assert_eq!(is_prime_trial(n), is_prime_trial_naive(n), "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn test_is_prime_trial_past_the_prime_list() {
+        // the smallest factors lie beyond the primes below 2^10
+        assert!(!is_prime_trial(1_031 * 1_033));
+        assert!(!is_prime_trial(65_521 * 65_537));
+        assert!(!is_prime_trial(4_294_967_291 * 3));
+        assert!(is_prime_trial(4_294_967_291));
+        assert!(is_prime_trial(1_000_000_007));
+        assert!(!is_prime_trial(1_000_000_007 * 1_009));
+    }
+
+    #[test]
+    fn test_is_prime_fast_strong_pseudoprimes() {
+        assert!(!is_prime_fast(561));
+        assert!(!is_prime_fast(2_047));
+        assert!(!is_prime_fast(3_215_031_751));
+        assert!(!is_prime_fast(3_825_123_056_546_413_051));
+    }
+
+    #[test]
+    fn test_is_prime_large_numbers() {
+        let is_prime = is_prime::<u64>;
+
+        assert!(is_prime(1_000_000_007));
+        assert!(is_prime(1_000_000_000_000_000_003));
+        assert!(is_prime(9_999_999_999_999_999_961));
+        assert!(is_prime(18_446_744_073_709_551_557));
+        assert!(!is_prime(1_000_000_007 * 998_244_353));
+        assert!(!is_prime(4_294_967_291 * 4_294_967_279));
+        assert!(!is_prime(u64::MAX));
+        assert!(!is_prime(u64::MAX - 1));
+    }
+
+    #[test]
+    fn test_primes_in_range_near_u64_max() {
+        let primes = primes_in_range(u64::MAX - 100..u64::MAX);
+
+        assert_eq!(
+            primes,
+            vec![
+                18_446_744_073_709_551_521,
+                18_446_744_073_709_551_533,
+                18_446_744_073_709_551_557
+            ]
+        );
+    }
+
+    #[test]
+    fn test_primes_in_range_into() {
+        let mut out = [0u64; 8];
+        assert_eq!(primes_in_range_into(10..30, &mut out), 6);
+        assert_eq!(out[..6], [11, 13, 17, 19, 23, 29]);
+        assert_eq!(primes_in_range_into(24..29, &mut out), 0);
+        assert_eq!(primes_in_range_into(0..100, &mut []), 0);
+
+        // a full buffer is continued from one past the last prime
+        let range = 1_000_000..1_100_000;
+        let mut collected = Vec::new();
+        let mut start = range.start;
+        loop {
+            let written = primes_in_range_into(start..range.end, &mut out);
+            collected.extend_from_slice(&out[..written]);
+            if written < out.len() {
+                break;
+            }
+            start = out[written - 1] + 1;
+        }
+        assert_eq!(collected, primes_in_range(range));
+    }
+
+    #[test]
+    fn test_n_primes_into() {
+        let mut out = [0u64; 10_000];
+        assert_eq!(n_primes_into(&mut out), 10_000);
+        assert_eq!(out[..], n_primes::<u64>(10_000)[..]);
+        assert_eq!(n_primes_into(&mut []), 0);
+    }
+
+    #[test]
+    fn test_all_primes() {
+        let primes = all_primes().take(10).collect::<Vec<u64>>();
+        assert_eq!(primes, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+
+        assert_eq!(all_primes().nth(1), Some(3));
+        assert_eq!(all_primes().nth(9_999), Some(104_729));
+        assert_eq!(
+            all_primes().take(20_000).collect::<Vec<u64>>(),
+            n_primes(20_000)
+        );
+    }
+
+    #[test]
+    fn test_primes_skip_while() {
+        let primes = all_primes()
+            .skip_while(|&p| p < 1_000)
+            .take(3)
+            .collect::<Vec<u64>>();
+
+        assert_eq!(primes, vec![1_009, 1_013, 1_019]);
+    }
+
+    #[test]
+    fn test_primes_starting_at() {
+        let primes = Primes::starting_at(1_000_000_000_000)
+            .take(3)
+            .collect::<Vec<u64>>();
+        assert_eq!(
+            primes,
+            vec![1_000_000_000_039, 1_000_000_000_061, 1_000_000_000_063]
+        );
+
+        assert_eq!(Primes::starting_at(7).next(), Some(7));
+        assert_eq!(Primes::starting_at(u64::MAX - 10).next(), None);
+    }
+
+    #[test]
+    fn test_next_and_prev_prime() {
+        assert_eq!(next_prime_after(0), Some(2));
+        assert_eq!(next_prime_after(2), Some(3));
+        assert_eq!(next_prime_after(1_000), Some(1_009));
+        assert_eq!(next_prime_after(u64::MAX - 59), Some(u64::MAX - 58));
+        assert_eq!(next_prime_after(u64::MAX - 58), None);
+        assert_eq!(next_prime_after(u64::MAX), None);
+
+        assert_eq!(prev_prime_before(0), None);
+        assert_eq!(prev_prime_before(2), None);
+        assert_eq!(prev_prime_before(3), Some(2));
+        assert_eq!(prev_prime_before(1_000), Some(997));
+        assert_eq!(prev_prime_before(u64::MAX), Some(u64::MAX - 58));
+    }
+
+    #[test]
+    fn test_primes_in_range_iterator() {
+        let primes = Primes::in_range(100..1_000).collect::<Vec<u64>>();
+        assert_eq!(primes, primes_in_range(100..1_000));
+
+        let reversed = Primes::in_range(100..1_000).rev().collect::<Vec<u64>>();
+        assert_eq!(reversed, primes.iter().rev().copied().collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn test_primes_double_ended() {
+        let mut primes = all_primes();
+        assert_eq!(primes.next_back(), Some(18_446_744_073_709_551_557));
+        assert_eq!(primes.next_back(), Some(18_446_744_073_709_551_533));
+        assert_eq!(primes.next(), Some(2));
+
+        let mut primes = Primes::in_range(0..30);
+        assert_eq!(primes.next(), Some(2));
+        assert_eq!(primes.next_back(), Some(29));
+        assert_eq!(primes.next(), Some(3));
+        assert_eq!(primes.next_back(), Some(23));
+
+        let rest = primes.collect::<Vec<u64>>();
+        assert_eq!(rest, vec![5, 7, 11, 13, 17, 19]);
+    }
+
+    #[test]
+    fn test_primes_ends_meet_inside_segment() {
+        let mut primes = Primes::in_range(0..200);
+        assert_eq!(primes.next(), Some(2));
+
+        let mut seen = vec![2];
+        while let Some(p) = primes.next_back() {
+            seen.push(p);
+        }
+        seen.sort();
+
+        assert_eq!(seen, primes_in_range(0..200));
+        assert_eq!(primes.next(), None);
+    }
+
+    #[test]
+    fn test_is_prime_generic() {
+        assert!(is_prime(251u8));
+        assert!(!is_prime(255u8));
+        assert!(is_prime(65_521u16));
+        assert!(is_prime(4_294_967_291u32));
+        assert!(!is_prime(u32::MAX));
+        assert!(is_prime(1_000_000_007usize));
+        assert!(is_prime(18_446_744_073_709_551_557u128));
+        assert!(is_prime(18_446_744_073_709_551_629u128));
+        assert!(is_prime(u128::MAX - 158));
+        assert!(!is_prime(u128::MAX));
+
+        for n in 0..=u16::MAX {
+            assert_eq!(is_prime(n), is_prime(n as u64));
+            assert_eq!(is_prime(n as u128), is_prime(n as u64));
+        }
+    }
+
+    #[test]
+    fn test_n_primes_generic() {
+        let primes: Vec<u8> = n_primes(54);
+        assert_eq!(primes.len(), 54);
+        assert_eq!(primes.last(), Some(&251));
+
+        let primes: Vec<u128> = n_primes(10);
+        assert_eq!(primes, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+    }
+
+    #[test]
+    #[should_panic(expected = "prime does not fit in the target type")]
+    fn test_n_primes_generic_overflow() {
+        n_primes::<u8>(55);
+    }
+
+    #[test]
+    fn test_primes_in_range_generic() {
+        assert_eq!(primes_in_range(240u8..255), vec![241, 251]);
+        assert_eq!(
+            primes_in_range(0usize..20),
+            vec![2, 3, 5, 7, 11, 13, 17, 19]
+        );
+
+        let around_u64_max = (u64::MAX as u128 - 60)..(u64::MAX as u128 + 30);
+        assert_eq!(
+            primes_in_range(around_u64_max),
+            vec![18_446_744_073_709_551_557, 18_446_744_073_709_551_629]
+        );
+    }
+
+    #[test]
+    fn test_primes_from_vec_generic() {
+        assert_eq!(
+            primes_from_vec(vec![1u32, 2, 4, 7, 9, 4_294_967_291]),
+            vec![2, 7, 4_294_967_291]
+        );
+        assert_eq!(
+            primes_from_vec(vec![u128::MAX, u128::MAX - 158]),
+            vec![u128::MAX - 158]
+        );
+        assert_eq!(primes_from_vec(Vec::<u16>::new()), vec![]);
+    }
+
+    #[test]
+    fn test_try_n_primes() {
+        assert_eq!(try_n_primes::<u64>(0), Ok(vec![]));
+        assert_eq!(try_n_primes::<u64>(5), Ok(vec![2, 3, 5, 7, 11]));
+        assert_eq!(*try_n_primes::<u8>(54).unwrap().last().unwrap(), 251);
+        assert_eq!(*try_n_primes::<u16>(6_542).unwrap().last().unwrap(), 65_521);
+    }
+
+    #[test]
+    fn test_try_n_primes_overflow() {
+        assert_eq!(
+            try_n_primes::<u8>(55),
+            Err(PrimesError::Overflow {
+                count: 55,
+                max: 255
+            })
+        );
+        assert_eq!(
+            try_n_primes::<u16>(6_543),
+            Err(PrimesError::Overflow {
+                count: 6_543,
+                max: 65_535
+            })
+        );
+        assert_eq!(
+            try_n_primes::<u32>(203_280_222),
+            Err(PrimesError::Overflow {
+                count: 203_280_222,
+                max: u32::MAX as u128
+            })
+        );
+        assert_eq!(
+            try_n_primes::<u64>(usize::MAX),
+            Err(PrimesError::Overflow {
+                count: usize::MAX,
+                max: u64::MAX as u128
+            })
+        );
+        assert_eq!(
+            try_n_primes::<u128>(usize::MAX),
+            Err(PrimesError::LimitTooLarge {
+                limit: usize::MAX as u128,
+                max: 425_656_284_035_217_743
+            })
+        );
+    }
+
+    #[test]
+    fn test_try_n_primes_limit() {
+        assert_eq!(
+            try_n_primes::<u64>(10usize.pow(15)),
+            Err(PrimesError::LimitTooLarge {
+                limit: 10u128.pow(15),
+                max: TRY_N_PRIMES_LIMIT as u128
+            })
+        );
+        assert_eq!(
+            try_n_primes::<u128>(TRY_N_PRIMES_LIMIT + 1),
+            Err(PrimesError::LimitTooLarge {
+                limit: TRY_N_PRIMES_LIMIT as u128 + 1,
+                max: TRY_N_PRIMES_LIMIT as u128
+            })
+        );
+    }
+
+    #[test]
+    fn test_try_primes_in_range_width_limit() {
+        let too_wide = |width: u128| {
+            Err(PrimesError::LimitTooLarge {
+                limit: width,
+                max: TRY_RANGE_WIDTH_LIMIT as u128,
+            })
+        };
+        assert_eq!(try_primes_in_range(..=u64::MAX), too_wide(1 << 64));
+        assert_eq!(try_primes_in_range(..), too_wide(1 << 64));
+        assert_eq!(try_primes_in_range(0..1 << 40), too_wide(1 << 40));
+        assert_eq!(
+            try_primes_in_range(10..=TRY_RANGE_WIDTH_LIMIT + 10),
+            too_wide(TRY_RANGE_WIDTH_LIMIT as u128 + 1)
+        );
+        assert_eq!(
+            try_primes_in_range(u64::MAX - TRY_RANGE_WIDTH_LIMIT..),
+            too_wide(TRY_RANGE_WIDTH_LIMIT as u128 + 1)
+        );
+    }
+
+    #[test]
+    fn test_try_primes_in_range_bounds() {
+        assert_eq!(try_primes_in_range(0..10), Ok(vec![2, 3, 5, 7]));
+        assert_eq!(try_primes_in_range(0..=11), Ok(vec![2, 3, 5, 7, 11]));
+        assert_eq!(try_primes_in_range(..=7), Ok(vec![2, 3, 5, 7]));
+        assert_eq!(try_primes_in_range(..7), Ok(vec![2, 3, 5]));
+        assert_eq!(try_primes_in_range(2..=2), Ok(vec![2]));
+        assert_eq!(try_primes_in_range(..=0), Ok(vec![]));
+        assert_eq!(
+            try_primes_in_range((Bound::Excluded(2), Bound::Included(11))),
+            Ok(vec![3, 5, 7, 11])
+        );
+        assert_eq!(
+            try_primes_in_range((Bound::Excluded(2), Bound::Excluded(11))),
+            Ok(vec![3, 5, 7])
+        );
+    }
+
+    #[test]
+    fn test_try_primes_in_range_top_of_u64() {
+        let top = vec![
+            18_446_744_073_709_551_521,
+            18_446_744_073_709_551_533,
+            18_446_744_073_709_551_557,
+        ];
+
+        assert_eq!(
+            try_primes_in_range(u64::MAX - 100..=u64::MAX),
+            Ok(top.clone())
+        );
+        assert_eq!(try_primes_in_range(u64::MAX - 100..), Ok(top.clone()));
+        assert_eq!(try_primes_in_range(u64::MAX - 100..u64::MAX), Ok(top));
+        assert_eq!(try_primes_in_range(u64::MAX..=u64::MAX), Ok(vec![]));
+        assert_eq!(
+            try_primes_in_range(18_446_744_073_709_551_557..=18_446_744_073_709_551_557),
+            Ok(vec![18_446_744_073_709_551_557])
+        );
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_try_primes_in_range_empty() {
+        assert_eq!(try_primes_in_range(5..5), Err(PrimesError::EmptyRange));
+        assert_eq!(try_primes_in_range(10..5), Err(PrimesError::EmptyRange));
+        assert_eq!(try_primes_in_range(5..=4), Err(PrimesError::EmptyRange));
+        assert_eq!(try_primes_in_range(..0), Err(PrimesError::EmptyRange));
+        assert_eq!(
+            try_primes_in_range((Bound::Excluded(u64::MAX), Bound::Unbounded)),
+            Err(PrimesError::EmptyRange)
+        );
+        assert_eq!(
+            try_primes_in_range((Bound::Excluded(5), Bound::Excluded(6))),
+            Err(PrimesError::EmptyRange)
+        );
+    }
+
+    #[test]
+    fn test_primes_error_display() {
+        assert_eq!(
+            PrimesError::Overflow {
+                count: 55,
+                max: 255
+            }
+            .to_string(),
+            "prime number 55 is larger than 255"
+        );
+        assert_eq!(PrimesError::EmptyRange.to_string(), "the range is empty");
+    }
+}
@@ -0,0 +1,160 @@
+#[cfg(test)]
+mod tests_modular {
+    use crate::math_utils::is_prime;
+    use crate::modular::{
+        crt, crt_pair, extended_gcd, gcd, lcm, mod_inv, mod_mul, mod_pow, sqrt_mod,
+    };
+
+    #[test]
+    fn test_mod_pow_matches_repeated_multiplication() {
+        for m in 1..40u64 {
+            for base in 0..m + 3 {
+                let mut expected = 1 % m;
+                for exp in 0..30u64 {
+                    assert_eq!(
+                        mod_pow(base, exp, m),
+                        expected,
+                        "{}^{} mod {}",
+                        base,
+                        exp,
+                        m
+                    );
+                    expected = expected * (base % m) % m;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mod_pow_large_modulus() {
+        let p = 18_446_744_073_709_551_557;
+        assert_eq!(mod_pow(2, p - 1, p), 1);
+        assert_eq!(mod_pow(u64::MAX, 2, p), mod_mul(u64::MAX, u64::MAX, p));
+        assert_eq!(mod_pow(0, 0, p), 1);
+        assert_eq!(mod_pow(5, 100, 1), 0);
+    }
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        assert_eq!(lcm(1 << 32, 3 << 31), 3 << 32);
+    }
+
+    #[test]
+    fn test_extended_gcd_bezout() {
+        for a in 0..60u64 {
+            for b in 0..60u64 {
+                let (g, x, y) = extended_gcd(a, b);
+                assert_eq!(g, gcd(a, b));
+                assert_eq!(a as i128 * x + b as i128 * y, g as i128);
+            }
+        }
+
+        let (g, x, y) = extended_gcd(u64::MAX, u64::MAX - 1);
+        assert_eq!(g, 1);
+        assert_eq!(u64::MAX as i128 * x + (u64::MAX - 1) as i128 * y, 1);
+    }
+
+    #[test]
+    fn test_mod_inv_against_brute_force() {
+        assert_eq!(mod_inv(3, 0), None);
+
+        for m in 1..80u64 {
+            for a in 0..2 * m {
+                let expected = (0..m).find(|&x| a * x % m == 1 % m);
+                assert_eq!(mod_inv(a, m), expected, "{}^-1 mod {}", a, m);
+            }
+        }
+
+        let p = 18_446_744_073_709_551_557;
+        let inverse = mod_inv(123_456_789, p).unwrap();
+        assert_eq!(mod_mul(inverse, 123_456_789, p), 1);
+    }
+
+    #[test]
+    fn test_crt_pair_against_brute_force() {
+        for m1 in 1..16u64 {
+            for m2 in 1..16u64 {
+                let l = lcm(m1, m2);
+                for a1 in 0..m1 {
+                    for a2 in 0..m2 {
+                        let expected = (0..l).find(|x| x % m1 == a1 && x % m2 == a2);
+                        assert_eq!(
+                            crt_pair(a1, m1, a2, m2),
+                            expected.map(|x| (x, l)),
+                            "x = {} mod {}, x = {} mod {}",
+                            a1,
+                            m1,
+                            a2,
+                            m2
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(5, 0)]), None);
+
+        // moduli whose product overflows, but whose lcm does not
+        let p = 4_294_967_291;
+        let q = 4_294_967_279;
+        assert_eq!(crt(&[(1, p * q), (1, p)]), Some((1, p * q)));
+        assert_eq!(crt(&[(1, p * q), (1, p * 3)]), None);
+
+        let (x, m) = crt(&[(17, p), (42, q)]).unwrap();
+        assert_eq!(m, p * q);
+        assert_eq!((x % p, x % q), (17, 42));
+    }
+
+    #[test]
+    fn test_sqrt_mod_against_brute_force() {
+        for p in (2..400u64).filter(|&p| is_prime(p)) {
+            for a in 0..p {
+                let roots: Vec<u64> = (0..p).filter(|x| x * x % p == a).collect();
+                assert_eq!(
+                    sqrt_mod(a, p),
+                    roots.first().copied(),
+                    "sqrt({}) mod {}",
+                    a,
+                    p
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_sqrt_mod_large_primes() {
+        // p - 1 divisible by a high power of two exercises the Tonelli-Shanks loop
+        for p in [
+            998_244_353,
+            18_446_744_069_414_584_321,
+            18_446_744_073_709_551_557,
+        ] {
+            for a in [2u64, 3, 5, 10, 123_456_789] {
+                let square = mod_mul(a, a, p);
+                let root = sqrt_mod(square, p).unwrap();
+                assert_eq!(mod_mul(root, root, p), square);
+                assert!(root == a % p || root == p - a % p);
+            }
+        }
+
+        assert_eq!(sqrt_mod(3, 7), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "prime modulus")]
+    fn test_sqrt_mod_composite_modulus() {
+        sqrt_mod(4, 15);
+    }
+}
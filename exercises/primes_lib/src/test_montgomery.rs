@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests_montgomery {
+    use crate::montgomery::{is_prime_u128, Montgomery};
+
+    const M127: u128 = (1 << 127) - 1;
+    const LARGEST_U128_PRIME: u128 = 340_282_366_920_938_463_463_374_607_431_768_211_297;
+
+    #[test]
+    fn test_round_trip() {
+        for n in [
+            3u128,
+            97,
+            1_000_000_007,
+            M127,
+            LARGEST_U128_PRIME,
+            u128::MAX,
+        ] {
+            let m = Montgomery::new(n);
+            for a in [0u128, 1, 2, n / 2, n - 1] {
+                assert_eq!(m.from_mont(m.to_mont(a)), a);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_matches_u128_arithmetic() {
+        let mut x: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut next = || {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            x
+        };
+
+        for _ in 0..1_000 {
+            let n = (next() | 1).max(3) as u128;
+            let (a, b) = (next() as u128 % n, next() as u128 % n);
+            let m = Montgomery::new(n);
+
+            let product = m.from_mont(m.mul(m.to_mont(a), m.to_mont(b)));
+            assert_eq!(product, a * b % n, "{} * {} mod {}", a, b, n);
+
+            assert_eq!(m.from_mont(m.add(m.to_mont(a), m.to_mont(b))), (a + b) % n);
+            assert_eq!(
+                m.from_mont(m.sub(m.to_mont(a), m.to_mont(b))),
+                (a + n - b) % n
+            );
+        }
+    }
+
+    #[test]
+    fn test_pow_mod_fermat() {
+        for p in [M127, LARGEST_U128_PRIME, 18_446_744_073_709_551_629] {
+            let m = Montgomery::new(p);
+            for a in [2u128, 3, 12_345_678_901_234_567_890, p - 2] {
+                assert_eq!(m.pow_mod(a, p - 1), 1);
+            }
+        }
+
+        let m = Montgomery::new(u128::MAX);
+        assert_eq!(m.pow_mod(2, 128), 1);
+        assert_eq!(m.pow_mod(2, 127), 1 << 127);
+        assert_eq!(m.pow_mod(0, 0), 1);
+    }
+
+    #[test]
+    fn test_half() {
+        let m = Montgomery::new(LARGEST_U128_PRIME);
+        for a in [1u128, 2, 3, LARGEST_U128_PRIME - 1] {
+            let half = m.half(m.to_mont(a));
+            assert_eq!(m.from_mont(m.add(half, half)), a);
+        }
+    }
+
+    #[test]
+    fn test_is_prime_u128() {
+        assert!(is_prime_u128(18_446_744_073_709_551_629));
+        assert!(is_prime_u128(9_223_372_036_854_775_837));
+        assert!(is_prime_u128(M127));
+        assert!(is_prime_u128(LARGEST_U128_PRIME));
+
+        assert!(!is_prime_u128(0));
+        assert!(!is_prime_u128(1));
+        assert!(!is_prime_u128(u128::MAX));
+        assert!(!is_prime_u128(LARGEST_U128_PRIME - 2));
+        assert!(!is_prime_u128(4_294_967_311 * 18_446_744_073_709_551_629));
+        assert!(!is_prime_u128(
+            9_223_372_036_854_775_837 * 9_223_372_036_854_775_837
+        ));
+        assert!(!is_prime_u128(
+            170_141_183_460_469_346_239_851_141_267_926_120_401
+        ));
+    }
+
+    #[test]
+    fn test_is_prime_u128_rejects_pseudoprimes() {
+        // strong pseudoprimes to base 2 - only the Lucas half of BPSW catches these
+        assert!(!is_prime_u128(147_574_056_656_752_341_661));
+        assert!(!is_prime_u128(147_574_247_971_905_036_253));
+        // Chernick Carmichael number (6k + 1)(12k + 1)(18k + 1)
+        assert!(!is_prime_u128(187_021_446_697_178_889_121));
+    }
+
+    #[test]
+    fn test_is_prime_u128_matches_u64_test() {
+        for n in 0..5_000u128 {
+            assert_eq!(
+                is_prime_u128(n),
+                crate::math_utils::is_prime_fast(n as u64),
+                "n = {}",
+                n
+            );
+        }
+    }
+}
@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests_parallel {
+    use std::panic;
+
+    use crate::math_utils::{primes_from_vec, primes_in_range};
+    use crate::parallel::{par_primes_from_vec, par_primes_in_range, run_chunks};
+
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_par_primes_in_range_matches_sequential() {
+        for threads in [1, 2, 4, 7] {
+            let range = 0u64..1_000_000;
+            assert_eq!(
+                par_primes_in_range(range.clone(), threads),
+                primes_in_range(range)
+            );
+        }
+    }
+
+    #[test]
+    fn test_par_primes_in_range_random_ranges() {
+        let mut rng = XorShift(0x2545_F491_4F6C_DD1D);
+
+        for _ in 0..20 {
+            let start = rng.next() % 1_000_000_000_000;
+            let len = rng.next() % 500_000;
+            let threads = (rng.next() % 8) as u32 + 1;
+
+            assert_eq!(
+                par_primes_in_range(start..start + len, threads),
+                primes_in_range(start..start + len),
+                "range {}..{} on {} threads",
+                start,
+                start + len,
+                threads
+            );
+        }
+    }
+
+    #[test]
+    fn test_par_primes_in_range_edge_cases() {
+        assert_eq!(par_primes_in_range(0u64..0, 4), vec![]);
+        assert_eq!(par_primes_in_range(0u64..3, 0), vec![2]);
+        assert_eq!(
+            par_primes_in_range(200u8..255, 3),
+            primes_in_range(200u8..255)
+        );
+
+        let around_u64_max = (u64::MAX as u128 - 100)..(u64::MAX as u128 + 100);
+        assert_eq!(
+            par_primes_in_range(around_u64_max.clone(), 4),
+            primes_in_range(around_u64_max)
+        );
+    }
+
+    #[test]
+    fn test_par_primes_from_vec_random_input() {
+        let mut rng = XorShift(0x9E37_79B9_7F4A_7C15);
+
+        for len in [0, 1, 10, 1_000, 50_000] {
+            let values: Vec<u64> = (0..len).map(|_| rng.next() >> (rng.next() % 64)).collect();
+
+            for threads in [1, 3, 8] {
+                assert_eq!(
+                    par_primes_from_vec(values.clone(), threads),
+                    primes_from_vec(values.clone())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_thread_count_is_clamped() {
+        assert_eq!(
+            par_primes_in_range(0u64..100_000, u32::MAX),
+            primes_in_range(0u64..100_000)
+        );
+        assert_eq!(par_primes_from_vec(vec![7u64, 8, 9], u32::MAX), vec![7]);
+    }
+
+    #[test]
+    fn test_panicking_chunk_is_raised_again() {
+        for threads in [1, 4] {
+            let result = panic::catch_unwind(|| {
+                run_chunks((0..10u64).collect(), threads, |n: u64| {
+                    assert_ne!(n, 3, "chunk 3 failed");
+                    vec![n]
+                })
+            });
+            let payload = result.unwrap_err();
+            let message = payload.downcast_ref::<String>().unwrap();
+            assert!(message.contains("chunk 3 failed"), "{}", message);
+        }
+    }
+}
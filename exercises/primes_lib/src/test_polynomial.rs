@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod tests_polynomial {
+    use crate::polynomial::{count_irreducible, irreducible_polynomials, Polynomial};
+
+    fn poly(coefficients: &[u64], p: u64) -> Polynomial {
+        Polynomial::new(coefficients.to_vec(), p)
+    }
+
+    /// Irreducibility by trial division with every monic polynomial of at most half the degree.
+    fn is_irreducible_naive(f: &Polynomial) -> bool {
+        let n = match f.degree() {
+            None | Some(0) => return false,
+            Some(n) => n,
+        };
+        (1..=n / 2).all(|d| irreducible_polynomials(d, f.modulus()).all(|g| !f.rem(&g).is_zero()))
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = poly(&[1, 2, 3], 7);
+        let b = poly(&[6, 0, 4, 1], 7);
+
+        assert_eq!(&a + &b, poly(&[0, 2, 0, 1], 7));
+        assert_eq!(&a - &a, Polynomial::zero(7));
+        assert_eq!(&a * &b, poly(&[6, 12, 22, 9, 14, 3], 7));
+        assert_eq!(poly(&[8, 15, 0, 0], 7), poly(&[1, 1], 7));
+        assert_eq!(poly(&[0, 0], 7).degree(), None);
+        assert_eq!(b.evaluate(2), (6 + 4 * 4 + 8) % 7);
+
+        let (q, r) = b.div_rem(&a);
+        assert_eq!(&(&q * &a) + &r, b);
+        assert!(r.degree() < a.degree());
+        assert_eq!(a.div_rem(&b), (Polynomial::zero(7), a.clone()));
+    }
+
+    #[test]
+    fn test_gcd_and_monic() {
+        // (x + 1)(x + 2) and (x + 1)(x + 3) over GF(5)
+        let f = &poly(&[1, 1], 5) * &poly(&[2, 1], 5);
+        let g = &poly(&[1, 1], 5) * &poly(&[3, 1], 5);
+        assert_eq!(f.gcd(&g), poly(&[1, 1], 5));
+        assert_eq!(poly(&[2, 4], 5).monic(), poly(&[3, 1], 5));
+        assert!(poly(&[3, 1], 5).is_monic());
+        assert_eq!(f.gcd(&Polynomial::zero(5)), f);
+    }
+
+    #[test]
+    fn test_pow_mod() {
+        // x^(p^n) = x modulo an irreducible polynomial of degree n
+        let f = poly(&[1, 2, 0, 1], 3);
+        assert!(f.is_irreducible());
+        assert_eq!(Polynomial::x(3).pow_mod(27, &f), Polynomial::x(3));
+        assert_eq!(Polynomial::x(3).pow_mod(0, &f), poly(&[1], 3));
+    }
+
+    #[test]
+    fn test_is_irreducible() {
+        assert!(poly(&[1, 1, 1], 2).is_irreducible());
+        assert!(!poly(&[1, 0, 1], 2).is_irreducible());
+        assert!(poly(&[1, 1, 0, 0, 1], 2).is_irreducible());
+        // x^4 + x^2 + 1 = (x^2 + x + 1)^2 over GF(2)
+        assert!(!poly(&[1, 0, 1, 0, 1], 2).is_irreducible());
+        // AES field polynomial x^8 + x^4 + x^3 + x + 1
+        assert!(poly(&[1, 1, 0, 1, 1, 0, 0, 0, 1], 2).is_irreducible());
+        // x^2 + 1 splits exactly when -1 is a square
+        assert!(poly(&[1, 0, 1], 7).is_irreducible());
+        assert!(!poly(&[1, 0, 1], 13).is_irreducible());
+        // not monic
+        assert!(poly(&[3, 0, 3], 7).is_irreducible());
+        assert!(!poly(&[4, 0, 3], 7).is_irreducible());
+        assert!(poly(&[5, 1], 7).is_irreducible());
+        assert!(!poly(&[5], 7).is_irreducible());
+        assert!(!Polynomial::zero(7).is_irreducible());
+    }
+
+    #[test]
+    fn test_matches_trial_division() {
+        for (p, max_degree) in [(2u64, 8), (3, 5), (5, 3)] {
+            for degree in 1..=max_degree {
+                let total = p.pow(degree as u32);
+                for index in 0..total {
+                    let mut coefficients: Vec<u64> =
+                        (0..degree).map(|i| index / p.pow(i as u32) % p).collect();
+                    coefficients.push(1);
+                    let f = Polynomial::new(coefficients, p);
+                    assert_eq!(
+                        f.is_irreducible(),
+                        is_irreducible_naive(&f),
+                        "{} over GF({})",
+                        f,
+                        p
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_irreducible_polynomials() {
+        let quadratics: Vec<String> = irreducible_polynomials(2, 3)
+            .map(|f| f.to_string())
+            .collect();
+        assert_eq!(quadratics, ["x^2 + 1", "x^2 + x + 2", "x^2 + 2x + 2"]);
+
+        for (p, degree, expected) in [(2, 2, 1), (2, 4, 3), (2, 8, 30), (3, 5, 48), (5, 3, 40)] {
+            assert_eq!(irreducible_polynomials(degree, p).count() as u64, expected);
+            assert_eq!(count_irreducible(degree as u32, p), Some(expected));
+        }
+        assert!(irreducible_polynomials(3, 5).all(|f| f.is_monic() && f.degree() == Some(3)));
+    }
+
+    #[test]
+    fn test_large_field() {
+        let p: u64 = 18_446_744_073_709_551_557;
+        // x^2 - 2 is irreducible iff 2 is not a square modulo p
+        let f = poly(&[p - 2, 0, 1], p);
+        assert_eq!(f.is_irreducible(), crate::modular::sqrt_mod(2, p).is_none());
+        assert_eq!(count_irreducible(2, p), None);
+        assert_eq!(count_irreducible(1, p), Some(p));
+    }
+
+    #[test]
+    #[should_panic(expected = "prime modulus")]
+    fn test_composite_modulus_panics() {
+        poly(&[1, 1], 9);
+    }
+}
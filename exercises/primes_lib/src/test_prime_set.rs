@@ -0,0 +1,173 @@
+#[cfg(test)]
+mod tests_prime_set {
+    use crate::math_utils::{is_prime, primes_in_range};
+    use crate::prime_set::PrimeSet;
+
+    #[test]
+    fn test_contains() {
+        let set = PrimeSet::new(10_000);
+
+        for n in 0..12_000u64 {
+            assert_eq!(set.contains(n), is_prime(n), "n = {}", n);
+        }
+        assert!(set.contains(18_446_744_073_709_551_557));
+    }
+
+    #[test]
+    fn test_len_and_tiny_limits() {
+        assert_eq!(PrimeSet::new(0).len(), 0);
+        assert!(PrimeSet::new(1).is_empty());
+        assert_eq!(PrimeSet::new(2).len(), 1);
+        assert_eq!(PrimeSet::new(3).len(), 2);
+        assert_eq!(PrimeSet::new(128).len(), 31);
+        assert_eq!(PrimeSet::new(1_000_000).len(), 78_498);
+        assert_eq!(PrimeSet::new(1_000_000).limit(), 1_000_000);
+    }
+
+    #[test]
+    fn test_next_and_prev_prime() {
+        let set = PrimeSet::new(1_000);
+
+        assert_eq!(set.next_prime_after(0), Some(2));
+        assert_eq!(set.next_prime_after(2), Some(3));
+        assert_eq!(set.next_prime_after(3), Some(5));
+        assert_eq!(set.next_prime_after(24), Some(29));
+        assert_eq!(set.next_prime_after(996), Some(997));
+        assert_eq!(set.next_prime_after(997), Some(1_009));
+        assert_eq!(set.next_prime_after(1_000_000), Some(1_000_003));
+        assert_eq!(set.next_prime_after(18_446_744_073_709_551_557), None);
+
+        assert_eq!(set.prev_prime_before(2), None);
+        assert_eq!(set.prev_prime_before(3), Some(2));
+        assert_eq!(set.prev_prime_before(4), Some(3));
+        assert_eq!(set.prev_prime_before(29), Some(23));
+        assert_eq!(set.prev_prime_before(1_009), Some(997));
+        assert_eq!(set.prev_prime_before(1_000_003), Some(999_983));
+        assert_eq!(
+            set.prev_prime_before(u64::MAX),
+            Some(18_446_744_073_709_551_557)
+        );
+    }
+
+    #[test]
+    fn test_next_prev_match_prime_list() {
+        let set = PrimeSet::new(5_000);
+        let primes = primes_in_range(0u64..6_000);
+
+        for n in 0..5_500u64 {
+            let next = primes.iter().copied().find(|&p| p > n);
+            let prev = primes.iter().copied().rev().find(|&p| p < n);
+            assert_eq!(set.next_prime_after(n), next, "next after {}", n);
+            assert_eq!(set.prev_prime_before(n), prev, "prev before {}", n);
+        }
+    }
+
+    #[test]
+    fn test_rank_and_select() {
+        let set = PrimeSet::new(100_000);
+        let primes = primes_in_range(0u64..100_001);
+
+        assert_eq!(set.rank(0), 0);
+        assert_eq!(set.rank(1), 0);
+        assert_eq!(set.rank(2), 1);
+        assert_eq!(set.rank(100_000), 9_592);
+        assert_eq!(set.select(0), None);
+
+        for (index, &p) in primes.iter().enumerate() {
+            let k = index as u64 + 1;
+            assert_eq!(set.select(k), Some(p));
+            assert_eq!(set.rank(p), k);
+            assert_eq!(set.rank(p - 1), k - 1);
+        }
+    }
+
+    #[test]
+    fn test_rank_and_select_above_limit() {
+        let set = PrimeSet::new(1_000);
+
+        assert_eq!(set.rank(1_000_000), 78_498);
+        assert_eq!(set.select(78_498), Some(999_983));
+        assert_eq!(set.select(169), Some(1_009));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_write_and_read_round_trip() {
+        for limit in [0, 1, 2, 127, 128, 129, 10_007] {
+            let set = PrimeSet::new(limit);
+
+            let mut bytes = Vec::new();
+            set.write_to(&mut bytes).unwrap();
+            let loaded = PrimeSet::read_from(bytes.as_slice()).unwrap();
+
+            assert_eq!(loaded, set);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_save_and_load_file() {
+        let path = std::env::temp_dir().join(format!("primes_lib_{}.primeset", std::process::id()));
+        let set = PrimeSet::new(1_000_000);
+
+        set.save(&path).unwrap();
+        let loaded = PrimeSet::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.len(), 78_498);
+        assert_eq!(loaded, set);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_read_rejects_invalid_data() {
+        let mut bytes = Vec::new();
+        PrimeSet::new(1_000).write_to(&mut bytes).unwrap();
+
+        let mut wrong_magic = bytes.clone();
+        wrong_magic[0] = b'X';
+        assert!(PrimeSet::read_from(wrong_magic.as_slice()).is_err());
+
+        let truncated = &bytes[..bytes.len() - 1];
+        assert!(PrimeSet::read_from(truncated).is_err());
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(PrimeSet::read_from(trailing.as_slice()).is_err());
+
+        let mut bits_above_limit = bytes.clone();
+        *bits_above_limit.last_mut().unwrap() = 0xFF;
+        assert!(PrimeSet::read_from(bits_above_limit.as_slice()).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_read_rejects_corrupted_limit() {
+        // a valid header and one word, but a limit that would need 2^57 words
+        let mut bytes = Vec::new();
+        PrimeSet::new(100).write_to(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), 25);
+        bytes[9..17].copy_from_slice(&u64::MAX.to_le_bytes());
+
+        let error = PrimeSet::read_from(bytes.as_slice()).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_read_rejects_one_marked_prime() {
+        let mut bytes = Vec::new();
+        PrimeSet::new(1_000).write_to(&mut bytes).unwrap();
+        // the first table byte follows the 17-byte header; its low bit is 1
+        bytes[17] |= 1;
+
+        let error = PrimeSet::read_from(bytes.as_slice()).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(error.to_string(), "PrimeSet table marks 1 as prime");
+
+        bytes[17] &= !1;
+        let set = PrimeSet::read_from(bytes.as_slice()).unwrap();
+        assert!(!set.contains(0) && !set.contains(1));
+        assert!(set.contains(2) && set.contains(3));
+    }
+}
@@ -0,0 +1,211 @@
+#[cfg(test)]
+mod tests_properties {
+    use std::thread;
+
+    use proptest::prelude::*;
+
+    use crate::big_primes::{baillie_psw, is_probable_prime};
+    use crate::bigint::BigUint;
+    use crate::certificate::{certify, verify_certificate};
+    use crate::counting::prime_pi;
+    use crate::factor::factorize;
+    use crate::math_utils::{
+        is_prime, is_prime_fast, is_prime_trial, n_primes, primes_from_vec, primes_in_range, Primes,
+    };
+    use crate::montgomery::is_prime_u128;
+    use crate::parallel::{par_primes_from_vec, par_primes_in_range};
+
+    /// Upper bound of the exhaustive check against the reference sieve.
+    const EXHAUSTIVE_LIMIT: usize = 10_000_000;
+
+    /// Number of threads the exhaustive check is split across.
+    const EXHAUSTIVE_THREADS: usize = 8;
+
+    /// Plain sieve of Eratosthenes over bytes - deliberately independent of `crate::sieve`.
+    fn reference_sieve(limit: usize) -> Vec<bool> {
+        let mut prime = vec![true; limit];
+        prime[0] = false;
+        prime[1] = false;
+        let mut p = 2;
+        while p * p < limit {
+            if prime[p] {
+                for multiple in (p * p..limit).step_by(p) {
+                    prime[multiple] = false;
+                }
+            }
+            p += 1;
+        }
+        prime
+    }
+
+    #[test]
+    fn test_is_prime_matches_reference_sieve() {
+        let reference = reference_sieve(EXHAUSTIVE_LIMIT);
+        let chunk = EXHAUSTIVE_LIMIT.div_ceil(EXHAUSTIVE_THREADS);
+
+        thread::scope(|scope| {
+            for (index, expected) in reference.chunks(chunk).enumerate() {
+                scope.spawn(move || {
+                    let start = (index * chunk) as u64;
+                    for (n, &expected) in (start..).zip(expected) {
+                        assert_eq!(is_prime(n), expected, "is_prime({})", n);
+                        assert_eq!(is_prime_fast(n), expected, "is_prime_fast({})", n);
+                    }
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn test_is_prime_trial_matches_reference_sieve() {
+        // is_prime only uses trial division below 2^20
+        let reference = reference_sieve(1 << 22);
+        for (n, &expected) in reference.iter().enumerate().skip(1 << 20) {
+            assert_eq!(is_prime_trial(n as u64), expected, "is_prime_trial({})", n);
+        }
+    }
+
+    /// Ranges of at most `max_len` values starting anywhere in `u64`, with a good
+    /// share of them pressed against `u64::MAX`.
+    fn any_range(max_len: u64) -> impl Strategy<Value = (u64, u64)> {
+        let start = prop_oneof![
+            0..1_000_000u64,
+            any::<u64>(),
+            u64::MAX - 10 * max_len..=u64::MAX,
+        ];
+        (start, 0..=max_len).prop_map(|(start, len)| (start, start.saturating_add(len)))
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn prop_n_primes_is_prefix(k in 0..3_000usize) {
+            let shorter = n_primes::<u64>(k);
+            let longer = n_primes::<u64>(k + 1);
+
+            prop_assert_eq!(&longer[..k], &shorter[..]);
+            prop_assert!(is_prime(longer[k]));
+            prop_assert!(shorter.last().is_none_or(|&p| p < longer[k]));
+        }
+
+        #[test]
+        fn prop_primes_in_range_matches_primes_from_vec((start, end) in any_range(2_000)) {
+            prop_assert_eq!(
+                primes_in_range(start..end),
+                primes_from_vec((start..end).collect())
+            );
+        }
+
+        #[test]
+        fn prop_primes_in_range_is_additive(
+            (start, end) in any_range(4_000),
+            split in 0.0..=1.0f64,
+        ) {
+            let middle = start + ((end - start) as f64 * split) as u64;
+            let mut joined = primes_in_range(start..middle);
+            joined.extend(primes_in_range(middle..end));
+
+            prop_assert_eq!(joined, primes_in_range(start..end));
+        }
+
+        #[test]
+        fn prop_primes_iterator_matches_range(
+            (start, end) in any_range(2_000),
+            sides in prop::collection::vec(any::<bool>(), 0..400),
+        ) {
+            let expected = primes_in_range(start..end);
+
+            prop_assert_eq!(Primes::in_range(start..end).collect::<Vec<_>>(), expected.clone());
+
+            let mut reversed: Vec<u64> = Primes::in_range(start..end).rev().collect();
+            reversed.reverse();
+            prop_assert_eq!(&reversed, &expected);
+
+            // both ends meet in the middle without losing or repeating a prime
+            let mut primes = Primes::in_range(start..end);
+            let (mut front, mut back) = (Vec::new(), Vec::new());
+            for &from_front in sides.iter().chain(std::iter::repeat(&true)) {
+                let next = if from_front { primes.next() } else { primes.next_back() };
+                match next {
+                    Some(p) if from_front => front.push(p),
+                    Some(p) => back.push(p),
+                    None => break,
+                }
+            }
+            front.extend(back.into_iter().rev());
+            prop_assert_eq!(front, expected);
+        }
+
+        #[test]
+        fn prop_parallel_matches_sequential(
+            (start, end) in any_range(20_000),
+            threads in 1..8u32,
+        ) {
+            prop_assert_eq!(
+                par_primes_in_range(start..end, threads),
+                primes_in_range(start..end)
+            );
+
+            let values: Vec<u64> = (start..end).step_by(7).collect();
+            prop_assert_eq!(
+                par_primes_from_vec(values.clone(), threads),
+                primes_from_vec(values)
+            );
+        }
+
+        #[test]
+        fn prop_factorize_multiplies_back(n in any::<u64>()) {
+            let factors = factorize(n);
+
+            let product = factors
+                .iter()
+                .try_fold(1u64, |acc, &(p, e)| acc.checked_mul(p.checked_pow(e)?));
+            prop_assert_eq!(product, Some(n.max(1)));
+            prop_assert!(factors.iter().all(|&(p, e)| is_prime(p) && e > 0));
+            prop_assert!(factors.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        }
+
+        #[test]
+        fn prop_prime_pi_differences_match_range_counts(
+            a in 0..100_000_000u64,
+            len in 0..20_000u64,
+        ) {
+            let b = a + len;
+            let count = primes_in_range(a + 1..b + 1).len() as u64;
+            prop_assert_eq!(prime_pi(b) - prime_pi(a), count);
+        }
+
+        #[test]
+        fn prop_certificates_verify(n in any::<u64>()) {
+            match certify(n) {
+                Some(certificate) => {
+                    prop_assert!(is_prime(n));
+                    prop_assert_eq!(certificate.prime, n);
+                    prop_assert!(verify_certificate(&certificate));
+                }
+                None => prop_assert!(!is_prime(n)),
+            }
+
+            // random u64 values are rarely prime, so certify the next one as well
+            if let Some(p) = (n..=u64::MAX).find(|&p| is_prime_fast(p)) {
+                let certificate = certify(p).unwrap();
+                prop_assert!(verify_certificate(&certificate));
+            }
+        }
+
+        #[test]
+        fn prop_wide_tests_agree(n in any::<u128>()) {
+            prop_assert_eq!(is_prime(n), is_prime_u128(n));
+            prop_assert_eq!(is_probable_prime(&BigUint::from(n)), is_prime_u128(n));
+        }
+
+        #[test]
+        fn prop_wide_tests_agree_below_2_64(n in any::<u64>().prop_map(|n| n | 1)) {
+            // is_probable_prime would answer with is_prime_fast here
+            let expected = is_prime_fast(n);
+            prop_assert_eq!(is_prime_u128(n as u128), expected);
+            prop_assert_eq!(baillie_psw(&BigUint::from(n)), expected);
+        }
+    }
+}
@@ -0,0 +1,129 @@
+#[cfg(test)]
+mod tests_sieve {
+    use crate::math_utils::is_prime_trial;
+    use crate::sieve::{
+        sieve_first, sieve_range, simple_sieve, SegmentedSieve, StackSieve, SEGMENT_SIZE,
+        STACK_WINDOW_WORDS,
+    };
+
+    fn primes_by_trial_division(range: std::ops::Range<u64>) -> Vec<u64> {
+        range.filter(|&n| is_prime_trial(n)).collect()
+    }
+
+    #[test]
+    fn test_simple_sieve() {
+        assert_eq!(simple_sieve(0), Vec::<u32>::new());
+        assert_eq!(simple_sieve(1), Vec::<u32>::new());
+        assert_eq!(simple_sieve(2), vec![2]);
+        assert_eq!(simple_sieve(30), vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+    }
+
+    #[test]
+    fn test_sieve_range_small_ranges() {
+        assert_eq!(sieve_range(0..0), Vec::new());
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 10..5;
+        assert_eq!(sieve_range(reversed), Vec::new());
+        assert_eq!(sieve_range(0..2), Vec::new());
+        assert_eq!(sieve_range(0..3), vec![2]);
+        assert_eq!(sieve_range(2..3), vec![2]);
+        assert_eq!(sieve_range(3..4), vec![3]);
+        assert_eq!(sieve_range(24..29), Vec::new());
+        assert_eq!(sieve_range(24..30), vec![29]);
+
+        for start in 0..50 {
+            for end in start..120 {
+                assert_eq!(
+                    sieve_range(start..end),
+                    primes_by_trial_division(start..end)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_sieve_range_crosses_segments() {
+        let span = 2 * SEGMENT_SIZE as u64;
+        let range = (span - 1000)..(3 * span + 1000);
+
+        assert_eq!(sieve_range(range.clone()), primes_by_trial_division(range));
+    }
+
+    #[test]
+    fn test_sieve_range_large_window() {
+        let start = 1_000_000_000_000;
+        let primes = sieve_range(start..start + 1000);
+
+        assert_eq!(primes, primes_by_trial_division(start..start + 1000));
+        assert_eq!(primes.first(), Some(&1_000_000_000_039));
+        assert_eq!(primes.len(), 37);
+    }
+
+    #[test]
+    fn test_sieve_range_above_base_prime_limit() {
+        let start = u64::MAX - 1000;
+        let primes = sieve_range(start..u64::MAX);
+
+        assert_eq!(primes.len(), 21);
+        assert_eq!(primes.last(), Some(&18_446_744_073_709_551_557));
+    }
+
+    #[test]
+    fn test_segmented_sieve_yields_segments_in_order() {
+        let mut sieve = SegmentedSieve::new(0..10 * SEGMENT_SIZE as u64);
+        let mut primes = Vec::new();
+        let mut segments = 0;
+
+        while sieve.next_segment(&mut primes) {
+            segments += 1;
+        }
+
+        assert!(sieve.is_finished());
+        assert_eq!(segments, 5);
+        assert!(primes.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(primes.len(), 28_223);
+    }
+
+    #[test]
+    fn test_sieve_first() {
+        assert_eq!(sieve_first(0), Vec::new());
+        assert_eq!(sieve_first(1), vec![2]);
+
+        let primes = sieve_first(10_000);
+        assert_eq!(primes.len(), 10_000);
+        assert_eq!(primes.last(), Some(&104_729));
+    }
+
+    #[test]
+    fn test_stack_sieve_small_ranges() {
+        for start in 0..50 {
+            for end in start..120 {
+                let primes: Vec<u64> = StackSieve::new(start..end).collect();
+                assert_eq!(primes, primes_by_trial_division(start..end));
+            }
+        }
+    }
+
+    #[test]
+    fn test_stack_sieve_matches_segmented_sieve() {
+        let span = 2 * 64 * STACK_WINDOW_WORDS as u64;
+        for range in [
+            0..3 * span + 17,
+            span - 1..span + 1,
+            1_000_000_000_000..1_000_000_050_000,
+            (1 << 32) - 30_000..(1 << 32) + 30_000,
+            u64::MAX - 20_000..u64::MAX,
+        ] {
+            let primes: Vec<u64> = StackSieve::new(range.clone()).collect();
+            assert_eq!(primes, sieve_range(range.clone()), "{:?}", range);
+        }
+    }
+
+    #[test]
+    fn test_stack_sieve_is_fused() {
+        let mut sieve = StackSieve::new(10..20);
+        assert_eq!(sieve.by_ref().count(), 4);
+        assert_eq!(sieve.next(), None);
+        assert_eq!(sieve.next(), None);
+    }
+}
@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod tests_small_primes {
+    use crate::math_utils::is_prime_trial_naive;
+    use crate::sieve::simple_sieve;
+    use crate::small_primes::{
+        is_small_prime, SMALL_PRIMES, SMALL_PRIME_TABLE_LIMIT, WHEEL_GAPS, WHEEL_MODULUS,
+    };
+
+    #[test]
+    fn test_table_matches_sieve() {
+        let primes = simple_sieve(SMALL_PRIME_TABLE_LIMIT);
+        let from_table: Vec<u32> = (0..=u16::MAX)
+            .filter(|&n| is_small_prime(n))
+            .map(|n| n as u32)
+            .collect();
+
+        assert_eq!(from_table, primes);
+    }
+
+    #[test]
+    fn test_table_edges() {
+        assert!(!is_small_prime(0));
+        assert!(!is_small_prime(1));
+        assert!(is_small_prime(2));
+        assert!(is_small_prime(65_521));
+        assert!(!is_small_prime(u16::MAX));
+        for n in 0..2_000u16 {
+            assert_eq!(
+                is_small_prime(n),
+                is_prime_trial_naive(n as u64),
+                "n = {}",
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn test_small_prime_list() {
+        let expected: Vec<u16> = simple_sieve(1 << 10)
+            .into_iter()
+            .map(|p| p as u16)
+            .collect();
+
+        assert_eq!(SMALL_PRIMES.to_vec(), expected);
+    }
+
+    #[test]
+    fn test_wheel_gaps() {
+        assert_eq!(
+            WHEEL_GAPS.iter().map(|&gap| gap as u64).sum::<u64>(),
+            WHEEL_MODULUS
+        );
+
+        let mut spoke = 1;
+        let mut spokes = vec![];
+        for &gap in &WHEEL_GAPS {
+            spoke += gap as u64;
+            spokes.push(spoke % WHEEL_MODULUS);
+        }
+        spokes.sort_unstable();
+
+        let coprime: Vec<u64> = (0..WHEEL_MODULUS)
+            .filter(|n| [2, 3, 5, 7].iter().all(|p| n % p != 0))
+            .collect();
+        assert_eq!(spokes, coprime);
+    }
+}
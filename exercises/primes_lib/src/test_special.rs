@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests_special {
+    use crate::big_primes::is_probable_prime;
+    use crate::bigint::BigUint;
+    use crate::math_utils::is_prime;
+    use crate::sieve::simple_sieve;
+    use crate::special::{llr, lucas_lehmer, mersenne, pepin, proth};
+
+    /// Exponents of the Mersenne primes below 2^4500.
+    const MERSENNE_EXPONENTS: [u64; 20] = [
+        2, 3, 5, 7, 13, 17, 19, 31, 61, 89, 107, 127, 521, 607, 1_279, 2_203, 2_281, 3_217, 4_253,
+        4_423,
+    ];
+
+    fn proth_number(k: u64, n: u64) -> BigUint {
+        (BigUint::from(k) << n) + 1
+    }
+
+    fn riesel_number(k: u64, n: u64) -> BigUint {
+        (BigUint::from(k) << n) - 1
+    }
+
+    #[test]
+    fn test_lucas_lehmer_finds_mersenne_exponents() {
+        let found: Vec<u64> = simple_sieve(1_300)
+            .into_iter()
+            .map(u64::from)
+            .filter(|&p| lucas_lehmer(p))
+            .collect();
+        assert_eq!(found, MERSENNE_EXPONENTS[..15]);
+
+        for p in MERSENNE_EXPONENTS[15..].iter().copied() {
+            assert!(lucas_lehmer(p), "2^{} - 1", p);
+        }
+        for p in [2_207, 3_221, 4_231, 4_421] {
+            assert!(!lucas_lehmer(p), "2^{} - 1", p);
+        }
+    }
+
+    #[test]
+    fn test_lucas_lehmer_matches_bpsw() {
+        for p in 0..200 {
+            let expected = p >= 2 && is_probable_prime(&mersenne(p));
+            assert_eq!(lucas_lehmer(p), expected, "2^{} - 1", p);
+        }
+    }
+
+    #[test]
+    fn test_pepin() {
+        for m in 0..=4 {
+            assert!(pepin(m), "F_{}", m);
+        }
+        for m in 5..=12 {
+            assert!(!pepin(m), "F_{}", m);
+        }
+    }
+
+    #[test]
+    fn test_proth_known_primes() {
+        // 3 * 2^n + 1 is prime for these n < 550 and no others
+        let expected = [
+            1, 2, 5, 6, 8, 12, 18, 30, 36, 41, 66, 189, 201, 209, 276, 353, 408, 438, 534,
+        ];
+        let found: Vec<u64> = (2..550).filter(|&n| proth(3, n)).collect();
+        assert_eq!(found, expected[1..]);
+        assert!(proth(1, 1));
+    }
+
+    #[test]
+    fn test_proth_matches_bpsw() {
+        for n in 1..12 {
+            for k in (1..1 << n).step_by(2) {
+                let expected = is_prime(k << n | 1);
+                assert_eq!(proth(k, n), expected, "{} * 2^{} + 1", k, n);
+            }
+        }
+        for n in 60..140 {
+            for k in [1, 3, 5, 9, 15, 27, 33, 63, 105, 255] {
+                let expected = is_probable_prime(&proth_number(k, n));
+                assert_eq!(proth(k, n), expected, "{} * 2^{} + 1", k, n);
+            }
+        }
+        // (2^40 + 1)^2 = (2^39 + 1) * 2^41 + 1
+        assert!(!proth((1 << 39) + 1, 41));
+    }
+
+    #[test]
+    fn test_llr_known_primes() {
+        // 3 * 2^n - 1 is prime for these n < 500 and no others
+        let expected = [
+            2, 3, 4, 6, 7, 11, 18, 34, 38, 43, 55, 64, 76, 94, 103, 143, 206, 216, 306, 324, 391,
+            458, 470,
+        ];
+        let found: Vec<u64> = (2..500).filter(|&n| llr(3, n)).collect();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn test_llr_matches_bpsw() {
+        for n in 1..12 {
+            for k in (1..1 << n).step_by(2) {
+                let expected = is_prime((k << n) - 1);
+                assert_eq!(llr(k, n), expected, "{} * 2^{} - 1", k, n);
+            }
+        }
+        for n in 60..140 {
+            for k in [1, 3, 5, 9, 15, 27, 33, 63, 105, 255] {
+                let expected = is_probable_prime(&riesel_number(k, n));
+                assert_eq!(llr(k, n), expected, "{} * 2^{} - 1", k, n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_llr_matches_lucas_lehmer() {
+        for p in [61, 67, 89, 101, 107, 127, 521, 523] {
+            assert_eq!(llr(1, p), lucas_lehmer(p), "2^{} - 1", p);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "k must be odd")]
+    fn test_even_k_panics() {
+        proth(4, 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "smaller than 2^n")]
+    fn test_large_k_panics() {
+        llr(17, 4);
+    }
+}